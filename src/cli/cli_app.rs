@@ -0,0 +1,443 @@
+// Std.
+use std::fs;
+use std::path::Path;
+
+// Custom.
+use crate::{
+    managers::{
+        cryproject_manager::{CryProject, CryProjectManager},
+        gfx_manager::GfxManager,
+        gfxexport_manager::GfxExportManager,
+        swf_manager::SwfManager,
+    },
+    misc::{config::ApplicationConfig, overrides::SettingOverrides},
+};
+
+const EXIT_CODE_SUCCESS: i32 = 0;
+const EXIT_CODE_FAILURE: i32 = 1;
+
+/// Command line interface, used when the app is started with a command.
+pub struct CliApp;
+
+impl CliApp {
+    /// Tells if the arguments specify a command, otherwise the GUI should be started
+    /// (for example, when the app is used to open a `.swf` file).
+    ///
+    /// ## Arguments
+    /// * `args`: command line arguments (without the path to the executable).
+    pub fn is_command(args: &[String]) -> bool {
+        match SettingOverrides::from_args(args) {
+            Ok((_, args)) => matches!(
+                args.first().map(String::as_str),
+                Some("report" | "export" | "config" | "help" | "--help" | "-h")
+            ),
+            // Let `run` report the error.
+            Err(_) => true,
+        }
+    }
+
+    /// Runs the specified command.
+    ///
+    /// ## Arguments
+    /// * `args`: command line arguments (without the path to the executable).
+    ///
+    /// ## Return
+    /// Process exit code.
+    pub fn run(args: &[String]) -> i32 {
+        #[cfg(windows)]
+        Self::attach_to_parent_console();
+
+        // Setting flags can be specified for any command.
+        let parsed_args = SettingOverrides::from_args(args);
+        if let Err(app_error) = parsed_args {
+            eprintln!("{}.\n", app_error.get_message());
+            Self::print_usage();
+            return EXIT_CODE_FAILURE;
+        }
+        let (cli_overrides, args) = parsed_args.unwrap();
+
+        match args.first().map(String::as_str) {
+            Some("report") => Self::report(&args[1..], cli_overrides),
+            Some("export") => Self::export(&args[1..], cli_overrides),
+            Some("config") if args.get(1).map(String::as_str) == Some("show") => {
+                Self::show_config(&args[2..], cli_overrides)
+            }
+            None | Some("help") | Some("--help") | Some("-h") => {
+                Self::print_usage();
+                EXIT_CODE_SUCCESS
+            }
+            Some(command) => {
+                eprintln!("Unknown command \"{}\".\n", command);
+                Self::print_usage();
+                EXIT_CODE_FAILURE
+            }
+        }
+    }
+
+    /// Prints information about the specified `.swf` and `.gfx` files.
+    fn report(paths_to_swf: &[String], cli_overrides: SettingOverrides) -> i32 {
+        if paths_to_swf.is_empty() {
+            eprintln!("Expected at least one path to a .swf or .gfx file.\n");
+            Self::print_usage();
+            return EXIT_CODE_FAILURE;
+        }
+
+        let mut app_config = ApplicationConfig::new();
+        app_config.cli_overrides = cli_overrides;
+
+        let mut exit_code = EXIT_CODE_SUCCESS;
+        for path_to_swf in paths_to_swf.iter() {
+            println!("{}:", path_to_swf);
+
+            let path = Path::new(path_to_swf);
+            if path
+                .extension()
+                .map(|extension| extension.eq_ignore_ascii_case("gfx"))
+                .unwrap_or(false)
+            {
+                if !Self::print_gfx_report(path) {
+                    exit_code = EXIT_CODE_FAILURE;
+                }
+                continue;
+            }
+
+            Self::load_project_config(&mut app_config, path);
+            Self::find_cry_project(path);
+            let project_fonts = ApplicationConfig::parse_font_list(&app_config.get_project_fonts());
+
+            let swf_info = SwfManager::read_info(path_to_swf);
+            if let Err(app_error) = swf_info {
+                eprintln!("    ERROR: {}", app_error.get_message());
+                exit_code = EXIT_CODE_FAILURE;
+                continue;
+            }
+            let swf_info = swf_info.unwrap();
+
+            println!(
+                "    stage size: {}x{}",
+                swf_info.stage_width, swf_info.stage_height
+            );
+
+            println!("    fonts:");
+            for line in swf_info.get_font_report(&project_fonts).iter() {
+                println!("        {}", line);
+            }
+
+            let swf_file_name = path.file_stem().unwrap_or_default().to_string_lossy();
+            let swf_dir = path.parent().unwrap_or_else(|| Path::new(""));
+            let profile = app_config.get_profile_for_swf_dir(&swf_dir.to_string_lossy());
+            println!("    images:");
+            for line in GfxExportManager::get_image_report(
+                &swf_file_name,
+                &swf_info.images,
+                &profile.additional_gfxexport_args,
+            ) {
+                println!("        {}", line);
+            }
+        }
+
+        exit_code
+    }
+
+    /// Exports the specified `.swf` files using GFxExport profiles from the config file,
+    /// files that were not changed since the last export are skipped unless `--force` is specified.
+    fn export(args: &[String], cli_overrides: SettingOverrides) -> i32 {
+        let mut force = false;
+        let mut path_to_output_dir = None;
+        let mut paths_to_swf = Vec::new();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--force" => force = true,
+                "--output-dir" => {
+                    let value = args.next();
+                    if value.is_none() {
+                        eprintln!("Expected a value after \"{}\".\n", arg);
+                        Self::print_usage();
+                        return EXIT_CODE_FAILURE;
+                    }
+                    path_to_output_dir = value.cloned();
+                }
+                _ => paths_to_swf.push(arg.clone()),
+            }
+        }
+
+        if paths_to_swf.is_empty() {
+            eprintln!("Expected at least one path to a .swf file.\n");
+            Self::print_usage();
+            return EXIT_CODE_FAILURE;
+        }
+
+        let mut app_config = ApplicationConfig::new();
+        app_config.cli_overrides = cli_overrides;
+
+        let mut exit_code = EXIT_CODE_SUCCESS;
+        for path_to_swf in paths_to_swf.iter() {
+            println!("{}:", path_to_swf);
+
+            let path = Path::new(path_to_swf);
+            Self::load_project_config(&mut app_config, path);
+
+            // Use the specified profile or the profile that was last used for this directory.
+            let (profile_name, profile_origin) = app_config.get_profile_name_for_swf_dir(
+                &path
+                    .parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .to_string_lossy(),
+            );
+            let profile = app_config.get_profile(&profile_name);
+            if profile.is_none() {
+                eprintln!(
+                    "    ERROR: unknown profile \"{}\" (from {}), available profiles: {}",
+                    profile_name,
+                    profile_origin,
+                    app_config.get_profile_names().join(", ")
+                );
+                exit_code = EXIT_CODE_FAILURE;
+                continue;
+            }
+            let profile = profile.unwrap();
+            if profile.path_to_gfxexport_bin.is_empty() {
+                eprintln!(
+                    "    ERROR: path to GFxExport is not specified in the profile \"{}\" \
+                    (see {})",
+                    profile.name,
+                    app_config.get_config_file_path()
+                );
+                exit_code = EXIT_CODE_FAILURE;
+                continue;
+            }
+
+            // Use output directory of the profile by default.
+            let path_to_output_dir = match &path_to_output_dir {
+                Some(path_to_output_dir) => path_to_output_dir.clone(),
+                None => {
+                    let path_to_project_config = app_config
+                        .project_config
+                        .as_ref()
+                        .map(|project_config| project_config.path_to_file.as_path());
+                    match profile.get_output_dirs(
+                        path,
+                        Self::find_cry_project(path).as_ref(),
+                        path_to_project_config,
+                    ) {
+                        Ok((path_to_gfx_dir, _)) => path_to_gfx_dir.to_string_lossy().to_string(),
+                        Err(app_error) => {
+                            eprintln!(
+                                "    ERROR: failed to determine output directory: {}, \
+                                please, use \"--output-dir\"",
+                                app_error.get_message()
+                            );
+                            exit_code = EXIT_CODE_FAILURE;
+                            continue;
+                        }
+                    }
+                }
+            };
+            if let Err(e) = fs::create_dir_all(&path_to_output_dir) {
+                eprintln!(
+                    "    ERROR: failed to create output directory \"{}\", error: {}",
+                    path_to_output_dir, e
+                );
+                exit_code = EXIT_CODE_FAILURE;
+                continue;
+            }
+
+            // Images are only used to record produced image files in the build manifest.
+            let images = SwfManager::read_info(path_to_swf)
+                .map(|swf_info| swf_info.images)
+                .unwrap_or_default();
+
+            let result = GfxExportManager::export(
+                &profile,
+                path_to_swf,
+                &path_to_output_dir,
+                &images,
+                force,
+            );
+            if let Err(app_error) = result {
+                eprintln!("    ERROR: {}", app_error.get_message());
+                exit_code = EXIT_CODE_FAILURE;
+                continue;
+            }
+            let result = result.unwrap();
+
+            if result.is_skipped {
+                println!("    up to date, skipped");
+            } else {
+                println!(
+                    "    exported to {} (profile \"{}\")",
+                    path_to_output_dir, profile.name
+                );
+            }
+            println!("    size: {}", result.summary.get_size_description());
+            for warning in result.summary.problems.iter().chain(result.warnings.iter()) {
+                eprintln!("    WARNING: {}", warning);
+            }
+        }
+
+        exit_code
+    }
+
+    /// Prints effective settings (for the specified `.swf` file if any) and where they come from.
+    fn show_config(paths_to_swf: &[String], cli_overrides: SettingOverrides) -> i32 {
+        let mut app_config = ApplicationConfig::new();
+        app_config.cli_overrides = cli_overrides;
+
+        println!("config file: {}", app_config.get_config_file_path());
+
+        let mut swf_dir = String::new();
+        if let Some(path_to_swf) = paths_to_swf.first() {
+            println!("{}:", path_to_swf);
+
+            let path = Path::new(path_to_swf);
+            Self::load_project_config(&mut app_config, path);
+            swf_dir = path
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .to_string_lossy()
+                .to_string();
+        }
+
+        for (name, value, origin) in app_config.get_effective_settings(&swf_dir) {
+            println!("    {} = \"{}\" (from {})", name, value, origin);
+        }
+
+        EXIT_CODE_SUCCESS
+    }
+
+    /// Loads the project config for the specified `.swf` file and prints its path.
+    fn load_project_config(app_config: &mut ApplicationConfig, path_to_swf: &Path) {
+        if let Err(app_error) = app_config.load_project_config(path_to_swf) {
+            eprintln!(
+                "    WARNING: {}, only user settings will be used",
+                app_error.get_message()
+            );
+        }
+
+        if let Some(project_config) = &app_config.project_config {
+            println!(
+                "    project config: {}",
+                project_config.path_to_file.to_string_lossy()
+            );
+        }
+    }
+
+    /// Looks for a CRYENGINE project of the specified `.swf` file and prints its root directory.
+    fn find_cry_project(path_to_swf: &Path) -> Option<CryProject> {
+        match CryProjectManager::find(path_to_swf) {
+            Ok(Some(cry_project)) => {
+                println!(
+                    "    CRYENGINE project: {}",
+                    cry_project.root_dir.to_string_lossy()
+                );
+                Some(cry_project)
+            }
+            Ok(None) => None,
+            Err(app_error) => {
+                eprintln!("    WARNING: {}", app_error.get_message());
+                None
+            }
+        }
+    }
+
+    /// Prints information about the specified `.gfx` file.
+    ///
+    /// ## Return
+    /// `false` if the file can't be read or has problems, `true` otherwise.
+    fn print_gfx_report(path_to_gfx: &Path) -> bool {
+        // Compare with the source movie if it's located next to the .gfx file.
+        let path_to_source_swf = path_to_gfx.with_extension("swf");
+        let path_to_source_swf = path_to_source_swf.to_string_lossy();
+
+        let summary =
+            GfxManager::read_summary(&path_to_gfx.to_string_lossy(), Some(&path_to_source_swf));
+        if let Err(app_error) = summary {
+            eprintln!("    ERROR: {}", app_error.get_message());
+            return false;
+        }
+        let summary = summary.unwrap();
+
+        for problem in summary.problems.iter() {
+            eprintln!("    WARNING: {}", problem);
+        }
+        for line in summary.get_report().iter() {
+            println!("    {}", line);
+        }
+
+        summary.problems.is_empty()
+    }
+
+    fn print_usage() {
+        println!(
+            "CRYENGINE UI Helper v{}\n\n\
+            Usage:\n    \
+                cryengine-ui-helper [<file.swf>]            start GUI (and open the file)\n    \
+                cryengine-ui-helper report <file.swf>...    print stage size, fonts and images\n    \
+                cryengine-ui-helper report <file.gfx>...    print summary of exported movies\n    \
+                cryengine-ui-helper export [--force] [--output-dir <dir>] <file.swf>...\n    \
+                                                            export movies that were changed since the last export\n    \
+                cryengine-ui-helper config show [<file.swf>]\n    \
+                                                            print effective settings and where they come from\n    \
+                cryengine-ui-helper help                    print this message\n\n\
+            Settings can be overridden by flags (for any command): {} <value>\n\
+            or by environment variables: {}\n\
+            (flags take precedence over environment variables, environment variables\n\
+            take precedence over the project config and the user config)",
+            env!("CARGO_PKG_VERSION"),
+            SettingOverrides::get_cli_flags().join(" <value>, "),
+            SettingOverrides::get_env_variables()
+                .iter()
+                .map(|(variable, setting)| format!("{} ({})", variable, setting))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+
+    /// Because the app uses the "windows" subsystem it has no console,
+    /// attach to the console of the parent process (if any) to see the output.
+    #[cfg(windows)]
+    fn attach_to_parent_console() {
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn AttachConsole(process_id: u32) -> i32;
+        }
+        const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+        unsafe {
+            AttachConsole(ATTACH_PARENT_PROCESS);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn is_command_only_for_known_commands() {
+        assert!(CliApp::is_command(&to_args(&["report", "menu.swf"])));
+        assert!(CliApp::is_command(&to_args(&[
+            "--profile",
+            "dds",
+            "export",
+            "menu.swf"
+        ])));
+        assert!(CliApp::is_command(&to_args(&["config", "show"])));
+        assert!(CliApp::is_command(&to_args(&["--help"])));
+        // Missing flag value is reported by the CLI.
+        assert!(CliApp::is_command(&to_args(&["--gfxexport"])));
+
+        assert!(!CliApp::is_command(&[]));
+        assert!(!CliApp::is_command(&to_args(&["menu.swf"])));
+        assert!(!CliApp::is_command(&to_args(&[
+            "--gfxexport",
+            "gfxexport.exe"
+        ])));
+    }
+}
@@ -0,0 +1,188 @@
+// External.
+use iced::{
+    widget::canvas::{self, Cursor, Frame, Geometry, Path, Stroke},
+    Color, Point, Rectangle, Size,
+};
+
+// Custom.
+use super::main_layout::{AlignMode, HAlign, VAlign};
+use crate::misc::theme::Theme;
+
+/// Element size used when neither the element size nor the SWF stage size is known.
+const DEFAULT_ELEMENT_SIZE: (u32, u32) = (1280, 720);
+/// Space between the borders of the canvas and the screen (in pixels).
+const SCREEN_MARGIN: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenResolution {
+    pub width: u32,
+    pub height: u32,
+    pub aspect_ratio: &'static str,
+}
+
+impl ScreenResolution {
+    pub const ALL: [ScreenResolution; 8] = [
+        ScreenResolution::new(1280, 720, "16:9"),
+        ScreenResolution::new(1920, 1080, "16:9"),
+        ScreenResolution::new(2560, 1440, "16:9"),
+        ScreenResolution::new(3840, 2160, "16:9"),
+        ScreenResolution::new(1920, 1200, "16:10"),
+        ScreenResolution::new(1024, 768, "4:3"),
+        ScreenResolution::new(2560, 1080, "21:9"),
+        ScreenResolution::new(3440, 1440, "21:9"),
+    ];
+
+    const fn new(width: u32, height: u32, aspect_ratio: &'static str) -> Self {
+        Self {
+            width,
+            height,
+            aspect_ratio,
+        }
+    }
+}
+
+impl Default for ScreenResolution {
+    fn default() -> Self {
+        Self::ALL[1]
+    }
+}
+
+impl std::fmt::Display for ScreenResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{} ({})", self.width, self.height, self.aspect_ratio)
+    }
+}
+
+/// Draws the target screen and the place where the element lands on it.
+pub struct AlignmentPreview {
+    pub screen: ScreenResolution,
+    /// Element size from the element settings or the SWF stage size (if known).
+    pub element_size: Option<(u32, u32)>,
+    pub align_mode: AlignMode,
+    pub halign: HAlign,
+    pub valign: VAlign,
+    pub scale: bool,
+    pub maximize: bool,
+}
+
+impl AlignmentPreview {
+    /// Calculates where the element is placed on the screen.
+    ///
+    /// This is an approximation of how CRYENGINE aligns elements:
+    /// - fullscreen elements are stretched to the screen,
+    /// - maximized dynamic elements cover the screen keeping the aspect ratio,
+    /// - scaled dynamic elements fit into the screen keeping the aspect ratio,
+    /// - other elements keep their size, the position depends on the alignment.
+    ///
+    /// ## Return
+    /// Element rectangle in screen pixels (may be outside of the screen).
+    pub fn get_element_rectangle(&self) -> Rectangle {
+        let screen = Size::new(self.screen.width as f32, self.screen.height as f32);
+        let (width, height) = self.element_size.unwrap_or(DEFAULT_ELEMENT_SIZE);
+        let element = Size::new(width.max(1) as f32, height.max(1) as f32);
+
+        let size = match self.align_mode {
+            AlignMode::Fullscreen => screen,
+            AlignMode::Dynamic if self.maximize => {
+                let factor = (screen.width / element.width).max(screen.height / element.height);
+                Size::new(element.width * factor, element.height * factor)
+            }
+            AlignMode::Dynamic if self.scale => {
+                let factor = (screen.width / element.width).min(screen.height / element.height);
+                Size::new(element.width * factor, element.height * factor)
+            }
+            _ => element,
+        };
+
+        let x = match self.halign {
+            HAlign::Left => 0.0,
+            HAlign::Center => (screen.width - size.width) / 2.0,
+            HAlign::Right => screen.width - size.width,
+        };
+        let y = match self.valign {
+            VAlign::Top => 0.0,
+            VAlign::Center => (screen.height - size.height) / 2.0,
+            VAlign::Bottom => screen.height - size.height,
+        };
+
+        Rectangle::new(Point::new(x, y), size)
+    }
+}
+
+impl<Message> canvas::Program<Message, Theme> for AlignmentPreview {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: Cursor,
+    ) -> Vec<Geometry> {
+        let palette = theme.palette();
+        let mut frame = Frame::new(bounds.size());
+
+        // Fit the screen into the canvas.
+        let factor = ((bounds.width - SCREEN_MARGIN * 2.0) / self.screen.width as f32)
+            .min((bounds.height - SCREEN_MARGIN * 2.0) / self.screen.height as f32)
+            .max(0.0);
+        let screen = Rectangle::new(
+            Point::new(
+                (bounds.width - self.screen.width as f32 * factor) / 2.0,
+                (bounds.height - self.screen.height as f32 * factor) / 2.0,
+            ),
+            Size::new(
+                self.screen.width as f32 * factor,
+                self.screen.height as f32 * factor,
+            ),
+        );
+        frame.fill_rectangle(screen.position(), screen.size(), palette.base.foreground);
+
+        // Draw the visible part of the element.
+        let element = self.get_element_rectangle();
+        let element = Rectangle::new(
+            Point::new(screen.x + element.x * factor, screen.y + element.y * factor),
+            Size::new(element.width * factor, element.height * factor),
+        );
+        if let Some(visible) = element.intersection(&screen) {
+            frame.fill_rectangle(
+                visible.position(),
+                visible.size(),
+                Color {
+                    a: 0.5,
+                    ..palette.normal.primary
+                },
+            );
+            frame.stroke(
+                &Path::rectangle(visible.position(), visible.size()),
+                Stroke::default()
+                    .with_color(palette.bright.primary)
+                    .with_width(2.0),
+            );
+        }
+
+        // Draw the screen border on top of the element.
+        frame.stroke(
+            &Path::rectangle(screen.position(), screen.size()),
+            Stroke::default()
+                .with_color(palette.bright.surface)
+                .with_width(1.0),
+        );
+
+        frame.fill_text(canvas::Text {
+            content: match self.element_size {
+                Some((width, height)) => format!("element {}x{}", width, height),
+                None => format!(
+                    "element size is unknown, assuming {}x{}",
+                    DEFAULT_ELEMENT_SIZE.0, DEFAULT_ELEMENT_SIZE.1
+                ),
+            },
+            position: Point::new(screen.x + 5.0, screen.y + 5.0),
+            color: palette.bright.surface,
+            size: 14.0,
+            ..canvas::Text::default()
+        });
+
+        vec![frame.into_geometry()]
+    }
+}
@@ -0,0 +1,68 @@
+/// Action that can be started from the command palette.
+pub struct PaletteCommand<Message> {
+    pub name: String,
+    /// Keyboard shortcut of the action (if any).
+    pub shortcut: Option<&'static str>,
+    pub message: Message,
+}
+
+impl<Message> PaletteCommand<Message> {
+    pub fn new(name: &str, shortcut: Option<&'static str>, message: Message) -> Self {
+        Self {
+            name: name.to_string(),
+            shortcut,
+            message,
+        }
+    }
+
+    /// Tells if all words of the query are found in the name (case insensitive).
+    fn is_matching(&self, query: &str) -> bool {
+        let name = self.name.to_lowercase();
+        query
+            .to_lowercase()
+            .split_whitespace()
+            .all(|word| name.contains(word))
+    }
+}
+
+/// Searchable list of actions of a layout.
+#[derive(Default)]
+pub struct CommandPalette {
+    is_shown: bool,
+    query: String,
+}
+
+impl CommandPalette {
+    /// Shows the palette with an empty query.
+    pub fn open(&mut self) {
+        self.is_shown = true;
+        self.query.clear();
+    }
+
+    pub fn close(&mut self) {
+        self.is_shown = false;
+    }
+
+    pub fn is_shown(&self) -> bool {
+        self.is_shown
+    }
+
+    pub fn get_query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+    }
+
+    /// Returns commands that match the query (all commands if the query is empty).
+    pub fn filter<'a, Message>(
+        &self,
+        commands: &'a [PaletteCommand<Message>],
+    ) -> Vec<&'a PaletteCommand<Message>> {
+        commands
+            .iter()
+            .filter(|command| command.is_matching(&self.query))
+            .collect()
+    }
+}
@@ -1,19 +1,38 @@
 // External.
 use iced::{
     alignment::{Horizontal, Vertical},
-    widget::{Button, Checkbox, Column, PickList, Row, Scrollable, Text, TextInput},
-    Command, Element, Length, Renderer,
+    keyboard::{KeyCode, Modifiers},
+    time,
+    widget::{
+        Button, Canvas, Checkbox, Column, Container, PickList, Row, Scrollable, Text, TextInput,
+    },
+    Command, Element, Length, Renderer, Subscription,
 };
-use native_dialog::{FileDialog, MessageDialog, MessageType};
+use rfd::AsyncFileDialog;
 
 // STD.
-use std::process;
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 // Custom.
+use super::alignment_preview::*;
+use super::command_palette::*;
+use super::notifications::*;
+use super::path_browser::*;
+use super::undo_history::*;
 use crate::{
-    managers::xml_manager::*,
-    misc::{config::ApplicationConfig, style, theme::Theme},
+    managers::{
+        cryproject_manager::*, gfx_manager::*, gfxexport_manager::*, session_manager::*,
+        swf_manager::*, xml_manager::*,
+    },
+    misc::{
+        config::{ApplicationConfig, GfxExportProfile, RecentFile, XML_PREVIEW_SPLIT_RANGE},
+        style,
+        theme::Theme,
+    },
     ApplicationMessage,
 };
 
@@ -31,6 +50,23 @@ const ELEMENT_NAME_SECTION_RIGHT_SIZE_PORTION: u16 = 4;
 // ----------------------------------------------
 const REMOVE_BUTTON_PORTION: u16 = 1;
 const LIST_ITEM_PORTION: u16 = 5;
+const ALIGNMENT_PREVIEW_HEIGHT: u16 = 200;
+/// Change of the XML preview width (in percent) when "<" or ">" is pressed.
+const XML_PREVIEW_SPLIT_STEP: u16 = 10;
+const MODAL_BUTTON_WIDTH: u16 = 100;
+const MODAL_MAX_WIDTH: u32 = 600;
+
+/// How often shown toasts are checked for expiration.
+const TOAST_EXPIRATION_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// How often the editing state is autosaved (if it was changed).
+const SESSION_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5);
+/// Files dropped within this interval are considered to be dropped together.
+const FILE_DROP_BATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+// Names of tabs stored in the config.
+const TAB_FUNCTIONS: &str = "functions";
+const TAB_EVENTS: &str = "events";
+const TAB_SWF_REPORT: &str = "swf_report";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VAlign {
@@ -94,6 +130,49 @@ impl VAlign {
     const ALL: [VAlign; 3] = [VAlign::Top, VAlign::Center, VAlign::Bottom];
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignMode {
+    Fullscreen,
+    Dynamic,
+    Fixed,
+}
+
+impl Default for AlignMode {
+    fn default() -> Self {
+        Self::Dynamic
+    }
+}
+
+impl std::fmt::Display for AlignMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AlignMode::Fullscreen => "fullscreen",
+                AlignMode::Dynamic => "dynamic",
+                AlignMode::Fixed => "fixed",
+            }
+        )
+    }
+}
+
+impl AlignMode {
+    const ALL: [AlignMode; 3] = [AlignMode::Fullscreen, AlignMode::Dynamic, AlignMode::Fixed];
+
+    /// Tells whether the element in this mode has a fixed width and height.
+    ///
+    /// ## Arguments
+    /// * `maximize`: whether the element is maximized (only used in dynamic mode).
+    pub fn has_fixed_size(&self, maximize: bool) -> bool {
+        match self {
+            AlignMode::Fullscreen => false,
+            AlignMode::Dynamic => !maximize,
+            AlignMode::Fixed => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParameterType {
     Any,
@@ -135,7 +214,7 @@ impl ParameterType {
     ];
 }
 
-#[derive(Default, Clone)]
+#[derive(Default, Clone, PartialEq)]
 pub struct UiParameter {
     pub name: String,
     pub description: String,
@@ -143,12 +222,137 @@ pub struct UiParameter {
 }
 
 /// Function or Event
-#[derive(Default, Clone)]
+#[derive(Default, Clone, PartialEq)]
 pub struct UiRunnable {
     pub name: String,
     pub parameters: Vec<UiParameter>, // array of pairs: name - description
 }
 
+impl UiRunnable {
+    /// Checks the name of the item: it should be a valid identifier that is unique in the list.
+    ///
+    /// ## Arguments
+    /// * `items`: list of functions or events.
+    /// * `index`: index of the item to check.
+    ///
+    /// ## Return
+    /// `None` if the name is valid, otherwise description of the problem.
+    pub fn get_name_error(items: &[UiRunnable], index: usize) -> Option<String> {
+        let name = &items[index].name;
+        if let Some(error) = get_identifier_error(name) {
+            return Some(error);
+        }
+
+        if items
+            .iter()
+            .enumerate()
+            .any(|(other_index, other)| other_index != index && other.name == *name)
+        {
+            return Some(format!("\"{}\" is used more than once", name));
+        }
+
+        None
+    }
+
+    /// Checks the name of the parameter: it should be a valid identifier that is unique
+    /// among parameters of this item.
+    ///
+    /// ## Return
+    /// `None` if the name is valid, otherwise description of the problem.
+    pub fn get_parameter_name_error(&self, param_index: usize) -> Option<String> {
+        let name = &self.parameters[param_index].name;
+        if let Some(error) = get_identifier_error(name) {
+            return Some(error);
+        }
+
+        if self
+            .parameters
+            .iter()
+            .enumerate()
+            .any(|(other_index, other)| other_index != param_index && other.name == *name)
+        {
+            return Some(format!("parameter \"{}\" is used more than once", name));
+        }
+
+        None
+    }
+
+    /// Tells if the name of the item or names/descriptions of its parameters
+    /// contain the filter.
+    ///
+    /// ## Arguments
+    /// * `filter`: text to find (case insensitive), empty to match all items.
+    pub fn is_matching_filter(&self, filter: &str) -> bool {
+        is_text_matching_filter(&self.name, filter)
+            || self.parameters.iter().any(|parameter| {
+                is_text_matching_filter(&parameter.name, filter)
+                    || is_text_matching_filter(&parameter.description, filter)
+            })
+    }
+}
+
+/// Tells if the text contains the filter (case insensitive).
+///
+/// ## Arguments
+/// * `filter`: text to find, empty to match any text.
+fn is_text_matching_filter(text: &str, filter: &str) -> bool {
+    filter.is_empty() || text.to_lowercase().contains(&filter.to_lowercase())
+}
+
+/// Checks that the name can be used by CRYENGINE: letters, digits and underscores,
+/// not starting with a digit.
+///
+/// ## Return
+/// `None` if the name is valid, otherwise description of the problem.
+fn get_identifier_error(name: &str) -> Option<String> {
+    let first_char = name.chars().next();
+    if first_char.is_none() {
+        return Some(String::from("name is empty"));
+    }
+
+    if first_char.unwrap().is_ascii_digit() {
+        return Some(format!("\"{}\" should not start with a digit", name));
+    }
+
+    if let Some(invalid_char) = name
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && *c != '_')
+    {
+        return Some(format!(
+            "\"{}\" contains \"{}\", only letters, digits and underscores are allowed",
+            name, invalid_char
+        ));
+    }
+
+    None
+}
+
+/// Values of the element that can be undone/redone.
+#[derive(Clone, PartialEq)]
+struct EditingState {
+    ui_elements_name: String,
+    ui_element_name: String,
+    functions: Vec<UiRunnable>,
+    events: Vec<UiRunnable>,
+    halign: Option<HAlign>,
+    valign: Option<VAlign>,
+    align_mode: Option<AlignMode>,
+    scale: bool,
+    maximize: bool,
+    width: u32,
+    height: u32,
+    gfx_layer: usize,
+}
+
+/// Fields that store paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathTarget {
+    GfxExportBin,
+    SwfFile,
+    GfxDir,
+    XmlDir,
+}
+
 enum EntityList {
     Functions,
     Events,
@@ -162,62 +366,225 @@ pub enum MainLayoutMessage {
     SelectPathToXmlOutput,
     ShowFunctions,
     ShowEvents,
+    ShowSwfReport,
     EntityListAddClicked,
     EntityListAddParameterClicked(usize), // item index
     GenerateClicked,
     AdditionalGfxExportArgsChanged(String),
+    ProjectFontsChanged(String),
+    ForceExportChanged(bool),
     UiElementsTextChanged(String),
     UiElementTextChanged(String),
     GfxLayerTextChanged(String),
     EntityListItemChanged(usize, String), // item index, item name
     EntityListRemoveItem(usize),
+    EntityListMoveItemUp(usize),                          // item index
+    EntityListMoveItemDown(usize),                        // item index
+    EntityListDuplicateItem(usize),                       // item index
+    EntityListMoveItemToOtherList(usize),                 // item index (functions <-> events)
     EntityListParameterNameChanged(usize, usize, String), // item index, param index, param name
     EntityListRemoveParameter(usize, usize),              // item index, param index
+    EntityListMoveParameterUp(usize, usize),              // item index, param index
+    EntityListMoveParameterDown(usize, usize),            // item index, param index
     EntityListParameterDescriptionChanged(usize, usize, String), // item index, param index, param desc
     EntityListParameterTypeChanged(usize, usize, ParameterType), // item index, param index, param type
     HorizontalAlignChanged(HAlign),
     VerticalAlignChanged(VAlign),
-    FullscreenChanged(bool),
+    AlignModeChanged(AlignMode),
+    ScaleChanged(bool),
+    MaximizeChanged(bool),
+    WidthTextChanged(String),
+    HeightTextChanged(String),
+    UseStageSizeClicked,
+    ProfileSelected(String),
+    GfxDirTemplateChanged(String),
+    XmlDirTemplateChanged(String),
+    RecentFileSelected(String),
+    RecentProjectSelected(String),
+    UndoClicked,
+    RedoClicked,
+    XmlPreviewToggled(bool),
+    AlignmentPreviewToggled(bool),
+    PreviewResolutionSelected(ScreenResolution),
+    ModalConfirmed,
+    ModalCancelled,
+    ToastClosed(usize), // toast index
+    ToastsExpired,
+    GenerateConfirmed(bool, bool), // invalid names confirmed, overwriting confirmed
+    LoadExistingXmlConfirmed(String), // path to the .xml file
+    RestoreSessionConfirmed,
+    RestoreSessionCancelled,
+    PathPicked(PathTarget, Option<String>), // `None` if nothing was picked
+    PathTextChanged(PathTarget, String),
+    PathTextSubmitted(PathTarget),
+    PathBrowserDirOpened(String), // path to the directory
+    PathBrowserParentDirOpened,
+    PathBrowserCancelled,
+    FileDropped(PathBuf),
+    OpenNextQueuedSwfFile,
+    SwfQueueCleared,
+    EntityListAddParameterToLastItemClicked,
+    SaveProjectClicked,
+    CommandPaletteOpened,
+    CommandPaletteClosed,
+    CommandPaletteQueryChanged(String),
+    CommandPaletteSubmitted, // runs the first found command
+    CommandPaletteCommandSelected(Box<MainLayoutMessage>),
+    EntityListFilterChanged(String),
+    EntityListParametersCollapsed(bool),
+    XmlPreviewSplitChanged(u16), // width of the XML preview in percent
+    SessionAutosaveTimerTicked,
 }
 
 pub struct MainLayout {
+    profile_name: String,
+    profile_names: Vec<String>,
     path_to_gfxexport_bin: String,
     path_to_swf_file: String,
     path_to_gfx_dir: String,
     path_to_xml_dir: String,
     additional_gfxexport_args: String,
+    project_fonts: String,
+    path_to_project_config: Option<String>,
+    cry_project: Option<CryProject>,
+    gfx_dir_template: String,
+    xml_dir_template: String,
+    output_dirs_error: Option<String>,
+    recent_files: Vec<RecentFile>,
+    recent_projects: Vec<String>,
     ui_elements_name: String,
     ui_element_name: String,
     current_list: EntityList,
+    is_swf_report_shown: bool,
     functions: Vec<UiRunnable>,
     events: Vec<UiRunnable>,
     halign: Option<HAlign>,
     valign: Option<VAlign>,
+    align_mode: Option<AlignMode>,
+    scale: bool,
+    maximize: bool,
+    width: u32,
+    height: u32,
     gfx_layer: usize,
-    fullscreen: bool,
+    swf_info: Option<SwfInfo>,
+    gfx_summary: Option<GfxSummary>,
+    force_export: bool,
+    /// Editing state that was autosaved last.
+    last_session: String,
+    /// Whether the last autosave failed (the warning is shown once until it succeeds).
+    is_session_autosave_failed: bool,
+    undo_history: UndoHistory<EditingState>,
+    is_xml_preview_shown: bool,
+    /// XML of the element compared with the existing .xml file.
+    xml_preview: Vec<DiffLine>,
+    xml_preview_title: String,
+    /// XML that is shown in the preview (to not compare it again if it's not changed).
+    xml_preview_source: String,
+    /// Path to the existing `.xml` file and its contents (`None` if there is no file)
+    /// or the error if it could not be read, the file is only read when the path changes.
+    existing_xml_cache: Option<(PathBuf, Option<Result<String, String>>)>,
+    /// Width of the XML preview (in percent of the tab width).
+    xml_preview_split: u16,
+    is_alignment_preview_shown: bool,
+    preview_resolution: ScreenResolution,
+    notifications: Notifications<MainLayoutMessage>,
+    /// Autosaved editing state that the user is asked to restore.
+    session_to_restore: Option<Session>,
+    /// Path to the .swf file typed by the user (opened on Enter).
+    swf_file_input: String,
+    path_browser: Option<(PathTarget, PathBrowser)>,
+    /// Whether the system file dialog is shown (new dialogs are not opened until it's closed).
+    is_path_picker_open: bool,
+    /// `.swf` files that were dropped together, opened one by one after generating.
+    swf_queue: Vec<PathBuf>,
+    last_file_drop_time: Option<Instant>,
+    command_palette: CommandPalette,
+    /// Text to find in names and descriptions of functions/events (shows only matching items).
+    entity_list_filter: String,
+    /// Whether items of the list are shown as single lines without parameter fields.
+    are_parameters_collapsed: bool,
 }
 
 impl MainLayout {
     pub fn new(app_config: &ApplicationConfig) -> Self {
-        Self {
-            path_to_gfxexport_bin: app_config.path_to_gfxexport_bin.clone(),
+        let profile = app_config.get_current_profile();
+
+        let mut layout = Self {
+            profile_name: profile.name.clone(),
+            profile_names: app_config.get_profile_names(),
+            path_to_gfxexport_bin: profile.path_to_gfxexport_bin.clone(),
             path_to_swf_file: String::new(),
             path_to_gfx_dir: String::new(),
             path_to_xml_dir: String::new(),
-            additional_gfxexport_args: app_config.additional_gfxexport_args.clone(),
+            additional_gfxexport_args: profile.additional_gfxexport_args.clone(),
+            project_fonts: app_config.get_project_fonts(),
+            path_to_project_config: None,
+            cry_project: None,
+            gfx_dir_template: profile.gfx_dir.clone(),
+            xml_dir_template: profile.xml_dir.clone(),
+            output_dirs_error: None,
+            recent_files: app_config.recent_files.clone(),
+            recent_projects: app_config.recent_projects.clone(),
             ui_elements_name: String::new(),
             ui_element_name: String::new(),
             functions: Vec::new(),
             events: Vec::new(),
-            current_list: EntityList::Functions,
+            current_list: if app_config.active_tab == TAB_EVENTS {
+                EntityList::Events
+            } else {
+                EntityList::Functions
+            },
+            is_swf_report_shown: app_config.active_tab == TAB_SWF_REPORT,
             halign: Some(HAlign::default()),
             valign: Some(VAlign::default()),
+            align_mode: Some(AlignMode::default()),
+            scale: false,
+            maximize: false,
+            width: 0,
+            height: 0,
             gfx_layer: 0,
-            fullscreen: false,
-        }
+            swf_info: None,
+            gfx_summary: None,
+            force_export: false,
+            last_session: String::new(),
+            is_session_autosave_failed: false,
+            undo_history: UndoHistory::default(),
+            is_xml_preview_shown: false,
+            xml_preview: Vec::new(),
+            xml_preview_title: String::new(),
+            xml_preview_source: String::new(),
+            existing_xml_cache: None,
+            xml_preview_split: app_config.xml_preview_split,
+            is_alignment_preview_shown: false,
+            preview_resolution: ScreenResolution::default(),
+            notifications: Notifications::default(),
+            session_to_restore: None,
+            swf_file_input: String::new(),
+            path_browser: None,
+            is_path_picker_open: false,
+            swf_queue: Vec::new(),
+            last_file_drop_time: None,
+            command_palette: CommandPalette::default(),
+            entity_list_filter: String::new(),
+            are_parameters_collapsed: false,
+        };
+        layout.last_session = SessionManager::serialize(&layout.get_session());
+
+        layout
     }
 
     pub fn view(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        // Modals and the path browser are shown instead of the layout.
+        if let Some(modal) = self.notifications.get_modal() {
+            return Self::get_modal(modal);
+        }
+        if let Some((target, path_browser)) = &self.path_browser {
+            return Self::get_path_browser(*target, path_browser);
+        }
+        if self.command_palette.is_shown() {
+            return self.get_command_palette();
+        }
+
         // Prepare buttons for categories.
         let mut functions_button = Button::new(Text::new("Functions").size(TEXT_SIZE))
             .on_press(MainLayoutMessage::ShowFunctions)
@@ -227,16 +594,66 @@ impl MainLayout {
             .on_press(MainLayoutMessage::ShowEvents)
             .style(style::Button::Inactive)
             .width(Length::Fill);
+        let mut swf_report_button =
+            Button::new(Text::new(self.get_swf_report_title()).size(TEXT_SIZE))
+                .on_press(MainLayoutMessage::ShowSwfReport)
+                .style(style::Button::Inactive)
+                .width(Length::Fill);
 
         // Highlight active.
-        match self.current_list {
-            EntityList::Functions => {
-                functions_button = functions_button.style(style::Button::Default)
+        if self.is_swf_report_shown {
+            swf_report_button = swf_report_button.style(style::Button::Default);
+        } else {
+            match self.current_list {
+                EntityList::Functions => {
+                    functions_button = functions_button.style(style::Button::Default)
+                }
+                EntityList::Events => events_button = events_button.style(style::Button::Default),
             }
-            EntityList::Events => events_button = events_button.style(style::Button::Default),
+        }
+
+        // Show either the list of functions/events or the report.
+        let mut tab_content = Row::new().push(
+            Container::new(if self.is_swf_report_shown {
+                self.get_swf_report()
+            } else {
+                self.get_entity_list()
+            })
+            .width(Length::FillPortion(if self.is_xml_preview_shown {
+                100 - self.xml_preview_split
+            } else {
+                1
+            })),
+        );
+        if self.is_xml_preview_shown {
+            tab_content = tab_content
+                .spacing(ELEMENT_SPACING)
+                .push(self.get_xml_preview());
         }
 
         Column::new()
+            .push(self.get_toasts())
+            .push(self.get_swf_queue_section())
+            .push(self.get_recent_section())
+            .push(
+                Row::new()
+                    .push(
+                        Text::new("GFxExport profile")
+                            .size(TEXT_SIZE)
+                            .width(Length::FillPortion(PATH_SECTION_LEFT_SIZE_PORTION)),
+                    )
+                    .spacing(ELEMENT_SPACING)
+                    .push(
+                        PickList::new(
+                            self.profile_names.clone(),
+                            Some(self.profile_name.clone()),
+                            MainLayoutMessage::ProfileSelected,
+                        )
+                        .text_size(TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
+                    ),
+            )
+            .spacing(ELEMENT_SPACING)
             .push(
                 Row::new()
                     .push(
@@ -246,12 +663,18 @@ impl MainLayout {
                     )
                     .spacing(ELEMENT_SPACING)
                     .push(
-                        Text::new(&self.path_to_gfxexport_bin)
-                            .size(SMALL_TEXT_SIZE)
-                            .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION))
-                            .vertical_alignment(Vertical::Center),
+                        TextInput::new("", &self.path_to_gfxexport_bin, |path: String| {
+                            MainLayoutMessage::PathTextChanged(PathTarget::GfxExportBin, path)
+                        })
+                        .on_submit(MainLayoutMessage::PathTextSubmitted(
+                            PathTarget::GfxExportBin,
+                        ))
+                        .padding(TEXT_INPUT_PADDING)
+                        .size(SMALL_TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
                     ),
             )
+            .push(self.get_gfxexport_bin_warning())
             .push(
                 Row::new()
                     .push(
@@ -272,6 +695,26 @@ impl MainLayout {
                     ),
             )
             .spacing(ELEMENT_SPACING)
+            .push(
+                Row::new()
+                    .push(
+                        Text::new("Project fonts (comma separated)")
+                            .size(TEXT_SIZE)
+                            .width(Length::FillPortion(PATH_SECTION_LEFT_SIZE_PORTION)),
+                    )
+                    .spacing(ELEMENT_SPACING)
+                    .push(
+                        TextInput::new(
+                            "",
+                            &self.project_fonts,
+                            MainLayoutMessage::ProjectFontsChanged,
+                        )
+                        .padding(TEXT_INPUT_PADDING)
+                        .size(TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
+                    ),
+            )
+            .spacing(ELEMENT_SPACING)
             .push(
                 Row::new()
                     .push(
@@ -281,10 +724,51 @@ impl MainLayout {
                     )
                     .spacing(ELEMENT_SPACING)
                     .push(
-                        Text::new(&self.path_to_swf_file)
-                            .size(SMALL_TEXT_SIZE)
-                            .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION))
-                            .vertical_alignment(Vertical::Center),
+                        TextInput::new("", &self.swf_file_input, |path: String| {
+                            MainLayoutMessage::PathTextChanged(PathTarget::SwfFile, path)
+                        })
+                        .on_submit(MainLayoutMessage::PathTextSubmitted(PathTarget::SwfFile))
+                        .padding(TEXT_INPUT_PADDING)
+                        .size(SMALL_TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
+                    ),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Row::new()
+                    .push(
+                        Text::new("Project config")
+                            .size(TEXT_SIZE)
+                            .width(Length::FillPortion(PATH_SECTION_LEFT_SIZE_PORTION)),
+                    )
+                    .spacing(ELEMENT_SPACING)
+                    .push(
+                        Text::new(match &self.path_to_project_config {
+                            Some(path) => format!("{} (overrides user settings)", path),
+                            None => String::from("not found, only user settings are used"),
+                        })
+                        .size(SMALL_TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION))
+                        .vertical_alignment(Vertical::Center),
+                    ),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Row::new()
+                    .push(
+                        Text::new("CRYENGINE project")
+                            .size(TEXT_SIZE)
+                            .width(Length::FillPortion(PATH_SECTION_LEFT_SIZE_PORTION)),
+                    )
+                    .spacing(ELEMENT_SPACING)
+                    .push(
+                        Text::new(match &self.cry_project {
+                            Some(cry_project) => cry_project.root_dir.to_string_lossy().to_string(),
+                            None => String::from("not found"),
+                        })
+                        .size(SMALL_TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION))
+                        .vertical_alignment(Vertical::Center),
                     ),
             )
             .spacing(ELEMENT_SPACING)
@@ -297,10 +781,13 @@ impl MainLayout {
                     )
                     .spacing(ELEMENT_SPACING)
                     .push(
-                        Text::new(&self.path_to_gfx_dir)
-                            .size(SMALL_TEXT_SIZE)
-                            .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION))
-                            .vertical_alignment(Vertical::Center),
+                        TextInput::new("", &self.path_to_gfx_dir, |path: String| {
+                            MainLayoutMessage::PathTextChanged(PathTarget::GfxDir, path)
+                        })
+                        .on_submit(MainLayoutMessage::PathTextSubmitted(PathTarget::GfxDir))
+                        .padding(TEXT_INPUT_PADDING)
+                        .size(SMALL_TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
                     ),
             )
             .spacing(ELEMENT_SPACING)
@@ -313,13 +800,18 @@ impl MainLayout {
                     )
                     .spacing(ELEMENT_SPACING)
                     .push(
-                        Text::new(&self.path_to_xml_dir)
-                            .size(SMALL_TEXT_SIZE)
-                            .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION))
-                            .vertical_alignment(Vertical::Center),
+                        TextInput::new("", &self.path_to_xml_dir, |path: String| {
+                            MainLayoutMessage::PathTextChanged(PathTarget::XmlDir, path)
+                        })
+                        .on_submit(MainLayoutMessage::PathTextSubmitted(PathTarget::XmlDir))
+                        .padding(TEXT_INPUT_PADDING)
+                        .size(SMALL_TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
                     ),
             )
             .spacing(ELEMENT_SPACING)
+            .push(self.get_output_dir_templates_section())
+            .spacing(ELEMENT_SPACING)
             .push(
                 Row::new()
                     .push(
@@ -362,6 +854,21 @@ impl MainLayout {
             .spacing(ELEMENT_SPACING)
             .push(
                 Row::new()
+                    .push(
+                        Text::new("Alignment mode:")
+                            .size(TEXT_SIZE)
+                            .vertical_alignment(Vertical::Center),
+                    )
+                    .spacing(ELEMENT_SPACING)
+                    .push(
+                        PickList::new(
+                            &AlignMode::ALL[..],
+                            self.align_mode,
+                            MainLayoutMessage::AlignModeChanged,
+                        )
+                        .text_size(TEXT_SIZE),
+                    )
+                    .spacing(ELEMENT_SPACING)
                     .push(
                         Text::new("Horizontal alignment:")
                             .size(TEXT_SIZE)
@@ -392,15 +899,6 @@ impl MainLayout {
                         .text_size(TEXT_SIZE),
                     )
                     .spacing(ELEMENT_SPACING)
-                    .push(
-                        Checkbox::new(
-                            self.fullscreen,
-                            "Fullscreen",
-                            MainLayoutMessage::FullscreenChanged,
-                        )
-                        .text_size(TEXT_SIZE),
-                    )
-                    .spacing(ELEMENT_SPACING)
                     .push(Text::new("GFx layer").size(TEXT_SIZE))
                     .spacing(ELEMENT_SPACING)
                     .push(
@@ -414,18 +912,34 @@ impl MainLayout {
                     ),
             )
             .spacing(ELEMENT_SPACING)
+            .push(self.get_size_section())
+            .spacing(ELEMENT_SPACING)
+            .push(self.get_alignment_preview())
+            .spacing(ELEMENT_SPACING)
+            .push(self.get_toolbar())
+            .spacing(ELEMENT_SPACING)
             .push(
                 Row::new()
                     .push(functions_button)
                     .spacing(ELEMENT_SPACING)
-                    .push(events_button),
+                    .push(events_button)
+                    .spacing(ELEMENT_SPACING)
+                    .push(swf_report_button),
             )
             .spacing(ELEMENT_SPACING)
-            .push(self.get_entity_list())
+            .push(tab_content)
             .spacing(ELEMENT_SPACING)
             .spacing(ELEMENT_SPACING)
             .push(
-                Button::new(Text::new("Generate .gfx and .xml files").size(TEXT_SIZE))
+                Checkbox::new(
+                    self.force_export,
+                    "Force GFxExport (export even if the .swf file was not changed)",
+                    MainLayoutMessage::ForceExportChanged,
+                )
+                .text_size(TEXT_SIZE),
+            )
+            .push(
+                Button::new(Text::new("Generate .gfx and .xml files (Ctrl+Enter)").size(TEXT_SIZE))
                     .on_press(MainLayoutMessage::GenerateClicked)
                     .style(style::Button::Special)
                     .width(Length::Fill),
@@ -446,11 +960,58 @@ impl MainLayout {
         message: MainLayoutMessage,
         app_config: &mut ApplicationConfig,
     ) -> Command<ApplicationMessage> {
+        // Only answers are accepted while a modal is shown (shortcuts are ignored),
+        // dropped files are still accepted to not lose some of the files dropped together.
+        if self.notifications.get_modal().is_some()
+            && !matches!(
+                message,
+                MainLayoutMessage::ModalConfirmed
+                    | MainLayoutMessage::ModalCancelled
+                    | MainLayoutMessage::ToastClosed(_)
+                    | MainLayoutMessage::ToastsExpired
+                    | MainLayoutMessage::SessionAutosaveTimerTicked
+                    | MainLayoutMessage::FileDropped(_)
+            )
+        {
+            if let MainLayoutMessage::PathPicked(_, path) = message {
+                // Don't change paths the question may refer to.
+                self.is_path_picker_open = false;
+                if let Some(path) = path {
+                    self.notifications.show_toast(
+                        NotificationKind::Warning,
+                        format!(
+                            "The picked path \"{}\" was ignored because a question is shown, \
+                            answer it and pick the path again",
+                            path
+                        ),
+                    );
+                }
+            }
+            return Command::none();
+        }
+
+        // Remember the state to be able to undo this message.
+        let is_history_message = matches!(
+            message,
+            MainLayoutMessage::UndoClicked | MainLayoutMessage::RedoClicked
+        );
+        let undo_group = Self::get_undo_group(&message);
+        let editing_state = self.get_editing_state();
+        let path_to_swf_file = self.path_to_swf_file.clone();
+
         match message {
-            MainLayoutMessage::SelectPathToGfxExportBin => self.select_gfx_bin_path(app_config),
-            MainLayoutMessage::SelectPathToSwfFile => self.select_swf_file_path(app_config),
-            MainLayoutMessage::SelectPathToGfxOutput => self.select_gfx_output_path(),
-            MainLayoutMessage::SelectPathToXmlOutput => self.select_xml_output_path(),
+            MainLayoutMessage::SelectPathToGfxExportBin => {
+                return self.pick_path(PathTarget::GfxExportBin, self.path_to_gfxexport_bin.clone())
+            }
+            MainLayoutMessage::SelectPathToSwfFile => {
+                return self.pick_path(PathTarget::SwfFile, app_config.last_used_swf_dir.clone())
+            }
+            MainLayoutMessage::SelectPathToGfxOutput => {
+                return self.pick_path(PathTarget::GfxDir, self.path_to_gfx_dir.clone())
+            }
+            MainLayoutMessage::SelectPathToXmlOutput => {
+                return self.pick_path(PathTarget::XmlDir, self.path_to_xml_dir.clone())
+            }
             MainLayoutMessage::UiElementsTextChanged(elements_name) => {
                 self.update_ui_elements_name(elements_name)
             }
@@ -461,21 +1022,62 @@ impl MainLayout {
             MainLayoutMessage::HorizontalAlignChanged(halign) => {
                 self.update_horizontal_align(halign)
             }
-            MainLayoutMessage::FullscreenChanged(fullscreen) => self.update_fullscreen(fullscreen),
-            MainLayoutMessage::ShowFunctions => self.show_functions(),
-            MainLayoutMessage::ShowEvents => self.show_events(),
+            MainLayoutMessage::AlignModeChanged(align_mode) => self.update_align_mode(align_mode),
+            MainLayoutMessage::ScaleChanged(scale) => self.update_scale(scale),
+            MainLayoutMessage::MaximizeChanged(maximize) => self.update_maximize(maximize),
+            MainLayoutMessage::WidthTextChanged(width) => self.update_width(width),
+            MainLayoutMessage::HeightTextChanged(height) => self.update_height(height),
+            MainLayoutMessage::UseStageSizeClicked => self.use_stage_size(),
+            MainLayoutMessage::ShowFunctions => self.show_functions(app_config),
+            MainLayoutMessage::ShowEvents => self.show_events(app_config),
+            MainLayoutMessage::ShowSwfReport => self.show_swf_report(app_config),
             MainLayoutMessage::EntityListAddClicked => self.add_list_item(),
             MainLayoutMessage::EntityListItemChanged(index, newname) => {
                 self.update_list_item(index, newname)
             }
             MainLayoutMessage::EntityListRemoveItem(name) => self.remove_list_item(name),
+            MainLayoutMessage::EntityListMoveItemUp(index) => self.move_list_item(index, true),
+            MainLayoutMessage::EntityListMoveItemDown(index) => self.move_list_item(index, false),
+            MainLayoutMessage::EntityListDuplicateItem(index) => self.duplicate_list_item(index),
+            MainLayoutMessage::EntityListMoveItemToOtherList(index) => {
+                self.move_list_item_to_other_list(index)
+            }
             MainLayoutMessage::AdditionalGfxExportArgsChanged(args) => {
                 self.update_additional_gfxexport_args(args)
             }
-            MainLayoutMessage::GenerateClicked => self.generate(app_config),
+            MainLayoutMessage::ProjectFontsChanged(fonts) => self.update_project_fonts(fonts),
+            MainLayoutMessage::ForceExportChanged(force) => self.update_force_export(force),
+            MainLayoutMessage::ProfileSelected(name) => self.select_profile(name, app_config),
+            MainLayoutMessage::GfxDirTemplateChanged(template) => {
+                self.update_gfx_dir_template(template)
+            }
+            MainLayoutMessage::XmlDirTemplateChanged(template) => {
+                self.update_xml_dir_template(template)
+            }
+            MainLayoutMessage::RecentFileSelected(label) => {
+                self.open_recent_file(label, app_config)
+            }
+            MainLayoutMessage::RecentProjectSelected(path) => {
+                return self.open_recent_project(path, app_config)
+            }
+            MainLayoutMessage::GenerateClicked => self.generate(app_config, false, false),
+            MainLayoutMessage::GenerateConfirmed(
+                is_invalid_names_confirmed,
+                is_overwrite_confirmed,
+            ) => self.generate(
+                app_config,
+                is_invalid_names_confirmed,
+                is_overwrite_confirmed,
+            ),
             MainLayoutMessage::EntityListRemoveParameter(item_index, param_index) => {
                 self.remove_list_parameter(item_index, param_index)
             }
+            MainLayoutMessage::EntityListMoveParameterUp(item_index, param_index) => {
+                self.move_list_parameter(item_index, param_index, true)
+            }
+            MainLayoutMessage::EntityListMoveParameterDown(item_index, param_index) => {
+                self.move_list_parameter(item_index, param_index, false)
+            }
             MainLayoutMessage::EntityListParameterNameChanged(item_index, param_index, newname) => {
                 self.update_list_parameter_name(item_index, param_index, newname)
             }
@@ -491,38 +1093,1460 @@ impl MainLayout {
                 self.add_list_item_parameter(item_index)
             }
             MainLayoutMessage::GfxLayerTextChanged(gfx_layer) => self.update_gfx_layer(gfx_layer),
+            MainLayoutMessage::UndoClicked => self.undo(),
+            MainLayoutMessage::RedoClicked => self.redo(),
+            MainLayoutMessage::XmlPreviewToggled(show) => self.is_xml_preview_shown = show,
+            MainLayoutMessage::AlignmentPreviewToggled(show) => {
+                self.is_alignment_preview_shown = show
+            }
+            MainLayoutMessage::PreviewResolutionSelected(resolution) => {
+                self.preview_resolution = resolution
+            }
+            MainLayoutMessage::ModalConfirmed => return self.close_modal(true, app_config),
+            MainLayoutMessage::ModalCancelled => return self.close_modal(false, app_config),
+            MainLayoutMessage::ToastClosed(index) => self.notifications.close_toast(index),
+            MainLayoutMessage::ToastsExpired => self.notifications.remove_expired_toasts(),
+            MainLayoutMessage::LoadExistingXmlConfirmed(path_to_xml_file) => {
+                self.get_data_from_existing_xml(&path_to_xml_file, false);
+                self.remember_recent_file(app_config);
+            }
+            MainLayoutMessage::RestoreSessionConfirmed => self.apply_session(app_config),
+            MainLayoutMessage::RestoreSessionCancelled => self.discard_session(app_config),
+            MainLayoutMessage::PathPicked(target, path) => {
+                self.is_path_picker_open = false;
+                self.path_browser = None;
+                if let Some(path) = path {
+                    self.set_path(target, path, app_config);
+                }
+            }
+            MainLayoutMessage::PathTextChanged(target, path) => self.update_path_text(target, path),
+            MainLayoutMessage::PathTextSubmitted(target) => {
+                self.submit_path_text(target, app_config)
+            }
+            MainLayoutMessage::PathBrowserDirOpened(path) => {
+                if let Some((_, path_browser)) = &mut self.path_browser {
+                    path_browser.open_dir(PathBuf::from(path));
+                }
+            }
+            MainLayoutMessage::PathBrowserParentDirOpened => {
+                if let Some((_, path_browser)) = &mut self.path_browser {
+                    path_browser.open_parent_dir();
+                }
+            }
+            MainLayoutMessage::PathBrowserCancelled => self.path_browser = None,
+            MainLayoutMessage::FileDropped(path) => self.open_dropped_file(path, app_config),
+            MainLayoutMessage::OpenNextQueuedSwfFile => self.open_next_queued_swf_file(app_config),
+            MainLayoutMessage::SwfQueueCleared => self.swf_queue.clear(),
+            MainLayoutMessage::EntityListAddParameterToLastItemClicked => {
+                let item_count = match self.current_list {
+                    EntityList::Functions => self.functions.len(),
+                    EntityList::Events => self.events.len(),
+                };
+                if item_count != 0 {
+                    self.add_list_item_parameter(item_count - 1);
+                }
+            }
+            MainLayoutMessage::SaveProjectClicked => self.save_project(app_config),
+            MainLayoutMessage::CommandPaletteOpened => self.command_palette.open(),
+            MainLayoutMessage::CommandPaletteClosed => self.command_palette.close(),
+            MainLayoutMessage::CommandPaletteQueryChanged(query) => {
+                self.command_palette.set_query(query)
+            }
+            MainLayoutMessage::CommandPaletteSubmitted => {
+                let commands = self.get_palette_commands();
+                let message = self
+                    .command_palette
+                    .filter(&commands)
+                    .first()
+                    .map(|command| command.message.clone());
+                if let Some(message) = message {
+                    return self.run_palette_command(message, app_config);
+                }
+            }
+            MainLayoutMessage::CommandPaletteCommandSelected(message) => {
+                return self.run_palette_command(*message, app_config)
+            }
+            MainLayoutMessage::EntityListFilterChanged(filter) => self.entity_list_filter = filter,
+            MainLayoutMessage::EntityListParametersCollapsed(is_collapsed) => {
+                self.are_parameters_collapsed = is_collapsed
+            }
+            MainLayoutMessage::XmlPreviewSplitChanged(split) => {
+                self.update_xml_preview_split(split, app_config)
+            }
+            MainLayoutMessage::SessionAutosaveTimerTicked => self.autosave_session(app_config),
+        }
+
+        if self.path_to_swf_file != path_to_swf_file {
+            // Another element was opened, its history starts from scratch.
+            self.undo_history.clear();
+        } else if !is_history_message {
+            self.undo_history
+                .add_step(editing_state, &self.get_editing_state(), undo_group);
         }
 
+        self.update_xml_preview();
+
         Command::none()
     }
 
-    fn get_entity_list(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
-        let mut list = Column::new();
+    /// Returns timers of the layout.
+    pub fn subscription(&self) -> Subscription<MainLayoutMessage> {
+        let mut subscriptions = vec![time::every(SESSION_AUTOSAVE_INTERVAL)
+            .map(|_| MainLayoutMessage::SessionAutosaveTimerTicked)];
 
-        // Get reference to vector to use.
-        let mut _vec_to_use = &self.functions;
-        match self.current_list {
-            EntityList::Functions => {
-                _vec_to_use = &self.functions;
-            }
-            EntityList::Events => {
-                _vec_to_use = &self.events;
+        if self.notifications.get_toast_count() != 0 {
+            subscriptions.push(
+                time::every(TOAST_EXPIRATION_CHECK_INTERVAL)
+                    .map(|_| MainLayoutMessage::ToastsExpired),
+            );
+        }
+
+        Subscription::batch(subscriptions)
+    }
+
+    /// Closes the shown modal and processes the answer.
+    ///
+    /// ## Arguments
+    /// * `is_confirmed`: whether the user answered "Yes".
+    fn close_modal(
+        &mut self,
+        is_confirmed: bool,
+        app_config: &mut ApplicationConfig,
+    ) -> Command<ApplicationMessage> {
+        match self.notifications.close_modal(is_confirmed) {
+            Some(message) => self.update(message, app_config),
+            None => Command::none(),
+        }
+    }
+
+    /// Closes the command palette and runs the selected command.
+    fn run_palette_command(
+        &mut self,
+        message: MainLayoutMessage,
+        app_config: &mut ApplicationConfig,
+    ) -> Command<ApplicationMessage> {
+        self.command_palette.close();
+        self.update(message, app_config)
+    }
+
+    /// Returns the message of the global keyboard shortcut (if the keys are a shortcut).
+    ///
+    /// Shortcuts are also listed in `get_palette_commands`.
+    pub fn get_shortcut_message(
+        key_code: KeyCode,
+        modifiers: Modifiers,
+    ) -> Option<MainLayoutMessage> {
+        if !modifiers.command() {
+            return match key_code {
+                KeyCode::Escape => Some(MainLayoutMessage::CommandPaletteClosed),
+                _ => None,
+            };
+        }
+
+        match key_code {
+            KeyCode::P if modifiers.shift() => Some(MainLayoutMessage::CommandPaletteOpened),
+            KeyCode::Enter => Some(MainLayoutMessage::GenerateClicked),
+            KeyCode::O => Some(MainLayoutMessage::SelectPathToSwfFile),
+            KeyCode::S => Some(MainLayoutMessage::SaveProjectClicked),
+            KeyCode::N if modifiers.shift() => {
+                Some(MainLayoutMessage::EntityListAddParameterToLastItemClicked)
             }
+            KeyCode::N => Some(MainLayoutMessage::EntityListAddClicked),
+            KeyCode::Key1 => Some(MainLayoutMessage::ShowFunctions),
+            KeyCode::Key2 => Some(MainLayoutMessage::ShowEvents),
+            KeyCode::Key3 => Some(MainLayoutMessage::ShowSwfReport),
+            KeyCode::Z if modifiers.shift() => Some(MainLayoutMessage::RedoClicked),
+            KeyCode::Z => Some(MainLayoutMessage::UndoClicked),
+            KeyCode::Y => Some(MainLayoutMessage::RedoClicked),
+            _ => None,
         }
+    }
 
-        // Fill list.
-        for (index, item) in _vec_to_use.iter().enumerate() {
-            // Collect parameters.
-            let mut params = Column::new();
-            for (param_index, parameter) in item.parameters.iter().enumerate() {
-                params = params.push(
-                            Row::new()
-                                    .push(PickList::new(
-                                &ParameterType::ALL[..],
-                                parameter.type_,
-                            move |type_: ParameterType| -> MainLayoutMessage {
-                                    MainLayoutMessage::EntityListParameterTypeChanged(index, param_index, type_)
-                                },
+    /// Returns all actions that can be started from the command palette.
+    fn get_palette_commands(&self) -> Vec<PaletteCommand<MainLayoutMessage>> {
+        let (item_name, other_item_name) = match self.current_list {
+            EntityList::Functions => ("function", "event"),
+            EntityList::Events => ("event", "function"),
+        };
+        let show_or_hide = |is_shown: bool| if is_shown { "Hide" } else { "Show" };
+        let enable_or_disable = |is_enabled: bool| if is_enabled { "Disable" } else { "Enable" };
+
+        let mut commands = vec![
+            PaletteCommand::new(
+                "Generate .gfx and .xml files",
+                Some("Ctrl+Enter"),
+                MainLayoutMessage::GenerateClicked,
+            ),
+            PaletteCommand::new(
+                "Open .swf file",
+                Some("Ctrl+O"),
+                MainLayoutMessage::SelectPathToSwfFile,
+            ),
+            PaletteCommand::new(
+                "Save project (profile and recent element)",
+                Some("Ctrl+S"),
+                MainLayoutMessage::SaveProjectClicked,
+            ),
+            PaletteCommand::new(
+                &format!("Add {}", item_name),
+                Some("Ctrl+N"),
+                MainLayoutMessage::EntityListAddClicked,
+            ),
+            PaletteCommand::new(
+                &format!("Add parameter to the last {}", item_name),
+                Some("Ctrl+Shift+N"),
+                MainLayoutMessage::EntityListAddParameterToLastItemClicked,
+            ),
+            PaletteCommand::new(
+                "Show functions",
+                Some("Ctrl+1"),
+                MainLayoutMessage::ShowFunctions,
+            ),
+            PaletteCommand::new("Show events", Some("Ctrl+2"), MainLayoutMessage::ShowEvents),
+            PaletteCommand::new(
+                "Show SWF report",
+                Some("Ctrl+3"),
+                MainLayoutMessage::ShowSwfReport,
+            ),
+            PaletteCommand::new("Undo", Some("Ctrl+Z"), MainLayoutMessage::UndoClicked),
+            PaletteCommand::new("Redo", Some("Ctrl+Y"), MainLayoutMessage::RedoClicked),
+            PaletteCommand::new(
+                "Select path to GFxExport",
+                None,
+                MainLayoutMessage::SelectPathToGfxExportBin,
+            ),
+            PaletteCommand::new(
+                "Select .gfx output directory",
+                None,
+                MainLayoutMessage::SelectPathToGfxOutput,
+            ),
+            PaletteCommand::new(
+                "Select .xml output directory",
+                None,
+                MainLayoutMessage::SelectPathToXmlOutput,
+            ),
+            PaletteCommand::new(
+                &format!("{} XML preview", show_or_hide(self.is_xml_preview_shown)),
+                None,
+                MainLayoutMessage::XmlPreviewToggled(!self.is_xml_preview_shown),
+            ),
+            PaletteCommand::new(
+                &format!(
+                    "{} alignment preview",
+                    show_or_hide(self.is_alignment_preview_shown)
+                ),
+                None,
+                MainLayoutMessage::AlignmentPreviewToggled(!self.is_alignment_preview_shown),
+            ),
+            PaletteCommand::new(
+                &format!("{} force GFxExport", enable_or_disable(self.force_export)),
+                None,
+                MainLayoutMessage::ForceExportChanged(!self.force_export),
+            ),
+            PaletteCommand::new(
+                &format!("{} scale", enable_or_disable(self.scale)),
+                None,
+                MainLayoutMessage::ScaleChanged(!self.scale),
+            ),
+            PaletteCommand::new(
+                &format!("{} maximize", enable_or_disable(self.maximize)),
+                None,
+                MainLayoutMessage::MaximizeChanged(!self.maximize),
+            ),
+            PaletteCommand::new(
+                "Use stage size as element size",
+                None,
+                MainLayoutMessage::UseStageSizeClicked,
+            ),
+            PaletteCommand::new(
+                if self.are_parameters_collapsed {
+                    "Expand all parameters"
+                } else {
+                    "Collapse all parameters"
+                },
+                None,
+                MainLayoutMessage::EntityListParametersCollapsed(!self.are_parameters_collapsed),
+            ),
+        ];
+        if !self.entity_list_filter.is_empty() {
+            commands.push(PaletteCommand::new(
+                &format!("Clear {} filter", item_name),
+                None,
+                MainLayoutMessage::EntityListFilterChanged(String::new()),
+            ));
+        }
+
+        // Commands for the items of the current list.
+        let items = match self.current_list {
+            EntityList::Functions => &self.functions,
+            EntityList::Events => &self.events,
+        };
+        for (index, item) in items.iter().enumerate() {
+            commands.push(PaletteCommand::new(
+                &format!("Add parameter to {} \"{}\"", item_name, item.name),
+                None,
+                MainLayoutMessage::EntityListAddParameterClicked(index),
+            ));
+            commands.push(PaletteCommand::new(
+                &format!("Duplicate {} \"{}\"", item_name, item.name),
+                None,
+                MainLayoutMessage::EntityListDuplicateItem(index),
+            ));
+            commands.push(PaletteCommand::new(
+                &format!(
+                    "Move {} \"{}\" to {}s",
+                    item_name, item.name, other_item_name
+                ),
+                None,
+                MainLayoutMessage::EntityListMoveItemToOtherList(index),
+            ));
+            commands.push(PaletteCommand::new(
+                &format!("Remove {} \"{}\"", item_name, item.name),
+                None,
+                MainLayoutMessage::EntityListRemoveItem(index),
+            ));
+        }
+
+        // Commands with values to choose from.
+        for align_mode in AlignMode::ALL {
+            commands.push(PaletteCommand::new(
+                &format!("Align mode: {}", align_mode),
+                None,
+                MainLayoutMessage::AlignModeChanged(align_mode),
+            ));
+        }
+        for halign in HAlign::ALL {
+            commands.push(PaletteCommand::new(
+                &format!("Horizontal align: {}", halign),
+                None,
+                MainLayoutMessage::HorizontalAlignChanged(halign),
+            ));
+        }
+        for valign in VAlign::ALL {
+            commands.push(PaletteCommand::new(
+                &format!("Vertical align: {}", valign),
+                None,
+                MainLayoutMessage::VerticalAlignChanged(valign),
+            ));
+        }
+        for resolution in ScreenResolution::ALL {
+            commands.push(PaletteCommand::new(
+                &format!("Preview resolution: {}", resolution),
+                None,
+                MainLayoutMessage::PreviewResolutionSelected(resolution),
+            ));
+        }
+        for profile_name in self.profile_names.iter() {
+            commands.push(PaletteCommand::new(
+                &format!("GFxExport profile: {}", profile_name),
+                None,
+                MainLayoutMessage::ProfileSelected(profile_name.clone()),
+            ));
+        }
+        for recent_file in self.recent_files.iter() {
+            let label = recent_file.get_label();
+            commands.push(PaletteCommand::new(
+                &format!("Reopen recent element: {}", label),
+                None,
+                MainLayoutMessage::RecentFileSelected(label),
+            ));
+        }
+        for recent_project in self.recent_projects.iter() {
+            commands.push(PaletteCommand::new(
+                &format!("Open recent project: {}", recent_project),
+                None,
+                MainLayoutMessage::RecentProjectSelected(recent_project.clone()),
+            ));
+        }
+
+        if !self.swf_queue.is_empty() {
+            commands.push(PaletteCommand::new(
+                "Open next queued .swf file",
+                None,
+                MainLayoutMessage::OpenNextQueuedSwfFile,
+            ));
+            commands.push(PaletteCommand::new(
+                "Clear queue of .swf files",
+                None,
+                MainLayoutMessage::SwfQueueCleared,
+            ));
+        }
+
+        commands
+    }
+
+    /// Returns the name of the text field that the message changes, consecutive changes
+    /// of the same field are grouped into a single undo step.
+    ///
+    /// ## Return
+    /// `None` if the message does not type into a field (always a separate undo step).
+    fn get_undo_group(message: &MainLayoutMessage) -> Option<String> {
+        match message {
+            MainLayoutMessage::UiElementsTextChanged(_) => Some(String::from("elements_name")),
+            MainLayoutMessage::UiElementTextChanged(_) => Some(String::from("element_name")),
+            MainLayoutMessage::GfxLayerTextChanged(_) => Some(String::from("gfx_layer")),
+            MainLayoutMessage::WidthTextChanged(_) => Some(String::from("width")),
+            MainLayoutMessage::HeightTextChanged(_) => Some(String::from("height")),
+            MainLayoutMessage::EntityListItemChanged(item_index, _) => {
+                Some(format!("item.{}", item_index))
+            }
+            MainLayoutMessage::EntityListParameterNameChanged(item_index, param_index, _) => {
+                Some(format!("parameter_name.{}.{}", item_index, param_index))
+            }
+            MainLayoutMessage::EntityListParameterDescriptionChanged(
+                item_index,
+                param_index,
+                _,
+            ) => Some(format!(
+                "parameter_description.{}.{}",
+                item_index, param_index
+            )),
+            _ => None,
+        }
+    }
+
+    fn get_editing_state(&self) -> EditingState {
+        EditingState {
+            ui_elements_name: self.ui_elements_name.clone(),
+            ui_element_name: self.ui_element_name.clone(),
+            functions: self.functions.clone(),
+            events: self.events.clone(),
+            halign: self.halign,
+            valign: self.valign,
+            align_mode: self.align_mode,
+            scale: self.scale,
+            maximize: self.maximize,
+            width: self.width,
+            height: self.height,
+            gfx_layer: self.gfx_layer,
+        }
+    }
+
+    fn set_editing_state(&mut self, state: EditingState) {
+        self.ui_elements_name = state.ui_elements_name;
+        self.ui_element_name = state.ui_element_name;
+        self.functions = state.functions;
+        self.events = state.events;
+        self.halign = state.halign;
+        self.valign = state.valign;
+        self.align_mode = state.align_mode;
+        self.scale = state.scale;
+        self.maximize = state.maximize;
+        self.width = state.width;
+        self.height = state.height;
+        self.gfx_layer = state.gfx_layer;
+    }
+
+    fn undo(&mut self) {
+        if let Some(state) = self.undo_history.undo(self.get_editing_state()) {
+            self.set_editing_state(state);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(state) = self.undo_history.redo(self.get_editing_state()) {
+            self.set_editing_state(state);
+        }
+    }
+
+    /// Offers to restore the editing state that was autosaved before the app was closed
+    /// (if the user declines, the last opened element is reopened).
+    ///
+    /// ## Return
+    /// `true` if the user was asked to restore the state, `false` if there is nothing to restore.
+    pub fn restore_session(&mut self, app_config: &mut ApplicationConfig) -> bool {
+        let path_to_session = Self::get_session_file_path(app_config);
+        if path_to_session.is_none() || !path_to_session.as_ref().unwrap().exists() {
+            return false;
+        }
+        let path_to_session = path_to_session.unwrap();
+
+        let session = SessionManager::read(&path_to_session);
+        if let Err(app_error) = session {
+            // Non-critical error.
+            self.notifications.show_toast(
+                NotificationKind::Warning,
+                format!(
+                    "Unsaved changes of the last run can't be restored, {}",
+                    app_error.get_message()
+                ),
+            );
+            self.clear_session(app_config);
+            return false;
+        }
+        let session = session.unwrap();
+
+        // Ask if the user wants to restore the state.
+        self.notifications.ask(
+            NotificationKind::Info,
+            format!(
+                "The app was closed without generating the element \"{}\" ({}), \
+                do you want to restore unsaved changes?",
+                session.element.ui_element_name, session.path_to_swf
+            ),
+            MainLayoutMessage::RestoreSessionConfirmed,
+            Some(MainLayoutMessage::RestoreSessionCancelled),
+        );
+        self.session_to_restore = Some(session);
+
+        true
+    }
+
+    /// Restores the autosaved editing state (after the user agreed to restore it).
+    fn apply_session(&mut self, app_config: &mut ApplicationConfig) {
+        let session = self.session_to_restore.take();
+        if session.is_none() {
+            return;
+        }
+        let session = session.unwrap();
+
+        if Path::new(&session.path_to_swf).is_file() {
+            self.open_swf_file(Path::new(&session.path_to_swf), app_config);
+            if let Some(profile) = app_config.get_profile(&session.profile) {
+                self.apply_profile(&profile);
+            }
+        } else {
+            self.swf_file_input = session.path_to_swf.clone();
+            self.path_to_swf_file = session.path_to_swf;
+        }
+
+        self.path_to_gfx_dir = session.path_to_gfx_dir;
+        self.path_to_xml_dir = session.path_to_xml_dir;
+        self.output_dirs_error = None;
+
+        let element = session.element;
+        self.ui_elements_name = element.ui_elements_name;
+        self.ui_element_name = element.ui_element_name;
+        self.gfx_layer = element.gfx_layer;
+        self.align_mode = Some(element.align_mode);
+        self.scale = element.scale;
+        self.maximize = element.maximize;
+        self.width = element.width;
+        self.height = element.height;
+        self.halign = Some(element.halign);
+        self.valign = Some(element.valign);
+        self.functions = element.functions;
+        self.events = element.events;
+
+        // Keep the file until the element is generated.
+        self.last_session = SessionManager::serialize(&self.get_session());
+    }
+
+    /// Removes the autosaved editing state (after the user declined to restore it)
+    /// and reopens the last element instead.
+    fn discard_session(&mut self, app_config: &mut ApplicationConfig) {
+        self.session_to_restore = None;
+        self.clear_session(app_config);
+        self.restore_last_element(app_config);
+    }
+
+    /// Saves the editing state (if it was changed) to restore it if the app crashes.
+    fn autosave_session(&mut self, app_config: &ApplicationConfig) {
+        let path_to_session = Self::get_session_file_path(app_config);
+        if path_to_session.is_none() {
+            return;
+        }
+
+        let session = SessionManager::serialize(&self.get_session());
+        if session == self.last_session {
+            return;
+        }
+
+        match SessionManager::save(&path_to_session.unwrap(), &session) {
+            Ok(()) => self.is_session_autosave_failed = false,
+            Err(app_error) => {
+                // Non-critical error.
+                if !self.is_session_autosave_failed {
+                    self.notifications.show_toast(
+                        NotificationKind::Warning,
+                        format!("Failed to autosave changes, {}", app_error.get_message()),
+                    );
+                }
+                self.is_session_autosave_failed = true;
+            }
+        }
+        self.last_session = session;
+    }
+
+    /// Removes the autosaved editing state (when changes were saved).
+    fn clear_session(&mut self, app_config: &ApplicationConfig) {
+        if let Some(path_to_session) = Self::get_session_file_path(app_config) {
+            if let Err(app_error) = SessionManager::remove(&path_to_session) {
+                // Non-critical error.
+                self.notifications.show_toast(
+                    NotificationKind::Warning,
+                    format!(
+                        "Failed to remove autosaved changes, {}",
+                        app_error.get_message()
+                    ),
+                );
+            }
+        }
+
+        self.last_session = SessionManager::serialize(&self.get_session());
+    }
+
+    fn get_session(&self) -> Session {
+        Session {
+            path_to_swf: self.path_to_swf_file.clone(),
+            path_to_gfx_dir: self.path_to_gfx_dir.clone(),
+            path_to_xml_dir: self.path_to_xml_dir.clone(),
+            profile: self.profile_name.clone(),
+            element: self.get_xml_config(String::new()),
+        }
+    }
+
+    /// Regenerates the XML preview (if shown) and compares it with the existing .xml file.
+    fn update_xml_preview(&mut self) {
+        if !self.is_xml_preview_shown {
+            return;
+        }
+
+        let file_name = Path::new(&self.path_to_swf_file)
+            .file_stem()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let xml = XmlManager::config_to_string(&self.get_xml_config(format!("{}.gfx", file_name)));
+        if let Err(app_error) = xml {
+            self.xml_preview = Vec::new();
+            self.xml_preview_title = format!("Failed to generate XML: {}", app_error.get_message());
+            return;
+        }
+        let xml = xml.unwrap();
+        let new_lines = || -> Vec<DiffLine> {
+            xml.lines()
+                .map(|line| DiffLine::Unchanged(line.to_string()))
+                .collect()
+        };
+
+        // Read the existing file only if another file is used (or it was rewritten).
+        let path_to_xml_file = Path::new(&self.path_to_xml_dir).join(format!("{}.xml", file_name));
+        let is_cached = matches!(
+            &self.existing_xml_cache,
+            Some((path, _)) if *path == path_to_xml_file
+        );
+        if is_cached && xml == self.xml_preview_source {
+            return;
+        }
+        if !is_cached {
+            let existing_xml = if file_name.is_empty()
+                || self.path_to_xml_dir.is_empty()
+                || !path_to_xml_file.is_file()
+            {
+                None
+            } else {
+                Some(fs::read_to_string(&path_to_xml_file).map_err(|e| e.to_string()))
+            };
+            self.existing_xml_cache = Some((path_to_xml_file.clone(), existing_xml));
+        }
+
+        // Compare with the existing file.
+        match &self.existing_xml_cache.as_ref().unwrap().1 {
+            None => {
+                self.xml_preview = new_lines();
+                self.xml_preview_title =
+                    String::from("XML preview (the .xml file does not exist yet)");
+            }
+            Some(Ok(existing_xml)) => {
+                self.xml_preview = XmlManager::get_diff(existing_xml, &xml);
+                let is_changed = self
+                    .xml_preview
+                    .iter()
+                    .any(|line| !matches!(line, DiffLine::Unchanged(_)));
+                self.xml_preview_title = format!(
+                    "XML preview ({} \"{}\")",
+                    if is_changed {
+                        "differences with"
+                    } else {
+                        "no differences with"
+                    },
+                    path_to_xml_file.to_string_lossy()
+                );
+            }
+            Some(Err(e)) => {
+                self.xml_preview = new_lines();
+                self.xml_preview_title = format!(
+                    "XML preview (failed to read \"{}\", error: {})",
+                    path_to_xml_file.to_string_lossy(),
+                    e
+                );
+            }
+        }
+        self.xml_preview_source = xml;
+    }
+
+    /// Returns XML data of the element.
+    ///
+    /// ## Arguments
+    /// * `gfx_file_name`: name of the .gfx file to reference.
+    fn get_xml_config(&self, gfx_file_name: String) -> XmlConfig {
+        XmlConfig {
+            ui_elements_name: self.ui_elements_name.clone(),
+            ui_element_name: self.ui_element_name.clone(),
+            gfx_file_name,
+            gfx_layer: self.gfx_layer,
+            align_mode: self.align_mode.unwrap_or_default(),
+            scale: self.scale,
+            maximize: self.maximize,
+            width: self.width,
+            height: self.height,
+            halign: self.halign.unwrap_or_default(),
+            valign: self.valign.unwrap_or_default(),
+            functions: self.functions.clone(),
+            events: self.events.clone(),
+        }
+    }
+
+    fn get_session_file_path(app_config: &ApplicationConfig) -> Option<PathBuf> {
+        app_config
+            .get_config_dir()
+            .map(|config_dir| config_dir.join(SESSION_FILE_NAME))
+    }
+
+    /// Warns if the specified GFxExport binary does not exist (the path is kept in the config).
+    fn get_gfxexport_bin_warning(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        if self.path_to_gfxexport_bin.is_empty() || Path::new(&self.path_to_gfxexport_bin).exists()
+        {
+            return Column::new().into();
+        }
+
+        Text::new("GFxExport is not found at the specified path, please, select it again.")
+            .size(SMALL_TEXT_SIZE)
+            .style(style::Text::Warning)
+            .into()
+    }
+
+    fn get_recent_section(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut column = Column::new();
+
+        if !self.recent_files.is_empty() {
+            column = column
+                .push(
+                    Row::new()
+                        .push(
+                            Text::new("Recent elements")
+                                .size(TEXT_SIZE)
+                                .width(Length::FillPortion(PATH_SECTION_LEFT_SIZE_PORTION)),
+                        )
+                        .spacing(ELEMENT_SPACING)
+                        .push(
+                            PickList::new(
+                                self.recent_files
+                                    .iter()
+                                    .map(RecentFile::get_label)
+                                    .collect::<Vec<String>>(),
+                                None,
+                                MainLayoutMessage::RecentFileSelected,
+                            )
+                            .placeholder("Reopen a recent element...")
+                            .text_size(TEXT_SIZE)
+                            .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
+                        ),
+                )
+                .spacing(ELEMENT_SPACING);
+        }
+
+        if !self.recent_projects.is_empty() {
+            column = column
+                .push(
+                    Row::new()
+                        .push(
+                            Text::new("Recent projects")
+                                .size(TEXT_SIZE)
+                                .width(Length::FillPortion(PATH_SECTION_LEFT_SIZE_PORTION)),
+                        )
+                        .spacing(ELEMENT_SPACING)
+                        .push(
+                            PickList::new(
+                                self.recent_projects.clone(),
+                                None,
+                                MainLayoutMessage::RecentProjectSelected,
+                            )
+                            .placeholder("Select a .swf file of a recent project...")
+                            .text_size(TEXT_SIZE)
+                            .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
+                        ),
+                )
+                .spacing(ELEMENT_SPACING);
+        }
+
+        column.into()
+    }
+
+    fn get_output_dir_templates_section(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut column = Column::new()
+            .push(
+                Row::new()
+                    .push(
+                        Text::new(".gfx directory template")
+                            .size(TEXT_SIZE)
+                            .width(Length::FillPortion(PATH_SECTION_LEFT_SIZE_PORTION)),
+                    )
+                    .spacing(ELEMENT_SPACING)
+                    .push(
+                        TextInput::new(
+                            "CRYENGINE project directory",
+                            &self.gfx_dir_template,
+                            MainLayoutMessage::GfxDirTemplateChanged,
+                        )
+                        .padding(TEXT_INPUT_PADDING)
+                        .size(TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
+                    ),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Row::new()
+                    .push(
+                        Text::new(".xml directory template")
+                            .size(TEXT_SIZE)
+                            .width(Length::FillPortion(PATH_SECTION_LEFT_SIZE_PORTION)),
+                    )
+                    .spacing(ELEMENT_SPACING)
+                    .push(
+                        TextInput::new(
+                            "CRYENGINE project directory",
+                            &self.xml_dir_template,
+                            MainLayoutMessage::XmlDirTemplateChanged,
+                        )
+                        .padding(TEXT_INPUT_PADDING)
+                        .size(TEXT_SIZE)
+                        .width(Length::FillPortion(PATH_SECTION_RIGHT_SIZE_PORTION)),
+                    ),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Text::new(
+                    "Variables: {swf_dir}, {swf_stem}, {project_root}, {profile}, \
+                    relative paths are relative to the .swf file directory.",
+                )
+                .size(SMALL_TEXT_SIZE),
+            );
+
+        if let Some(error) = &self.output_dirs_error {
+            column = column.push(
+                Text::new(error)
+                    .size(SMALL_TEXT_SIZE)
+                    .style(style::Text::Warning),
+            );
+        }
+
+        column.into()
+    }
+
+    fn get_modal(modal: &Modal<MainLayoutMessage>) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut buttons = Row::new();
+        if modal.on_confirm.is_some() {
+            buttons = buttons
+                .push(
+                    Button::new(
+                        Text::new("Yes")
+                            .size(TEXT_SIZE)
+                            .horizontal_alignment(Horizontal::Center),
+                    )
+                    .on_press(MainLayoutMessage::ModalConfirmed)
+                    .style(style::Button::Special)
+                    .width(Length::Units(MODAL_BUTTON_WIDTH)),
+                )
+                .spacing(ELEMENT_SPACING)
+                .push(
+                    Button::new(
+                        Text::new("No")
+                            .size(TEXT_SIZE)
+                            .horizontal_alignment(Horizontal::Center),
+                    )
+                    .on_press(MainLayoutMessage::ModalCancelled)
+                    .width(Length::Units(MODAL_BUTTON_WIDTH)),
+                );
+        } else {
+            buttons = buttons.push(
+                Button::new(
+                    Text::new("OK")
+                        .size(TEXT_SIZE)
+                        .horizontal_alignment(Horizontal::Center),
+                )
+                .on_press(MainLayoutMessage::ModalCancelled)
+                .width(Length::Units(MODAL_BUTTON_WIDTH)),
+            );
+        }
+
+        let mut title = Text::new(modal.kind.to_string()).size(TEXT_SIZE);
+        if modal.kind == NotificationKind::Error || modal.kind == NotificationKind::Warning {
+            title = title.style(style::Text::Warning);
+        }
+
+        Container::new(
+            Container::new(
+                Column::new()
+                    .push(title)
+                    .spacing(ELEMENT_SPACING)
+                    .push(Scrollable::new(Text::new(&modal.text).size(TEXT_SIZE)))
+                    .spacing(ELEMENT_SPACING)
+                    .push(buttons)
+                    .max_width(MODAL_MAX_WIDTH),
+            )
+            .padding(20)
+            .style(style::Container::Panel),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .padding(10)
+        .into()
+    }
+
+    fn get_path_browser(
+        target: PathTarget,
+        path_browser: &PathBrowser,
+    ) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut buttons = Row::new()
+            .push(
+                Button::new(Text::new("Up").size(TEXT_SIZE))
+                    .on_press(MainLayoutMessage::PathBrowserParentDirOpened),
+            )
+            .spacing(ELEMENT_SPACING);
+        if path_browser.is_dir_selection() {
+            buttons = buttons
+                .push(
+                    Button::new(Text::new("Select this directory").size(TEXT_SIZE))
+                        .on_press(MainLayoutMessage::PathPicked(
+                            target,
+                            Some(path_browser.get_current_dir().to_string_lossy().to_string()),
+                        ))
+                        .style(style::Button::Special),
+                )
+                .spacing(ELEMENT_SPACING);
+        }
+        buttons = buttons.push(
+            Button::new(Text::new("Cancel").size(TEXT_SIZE))
+                .on_press(MainLayoutMessage::PathBrowserCancelled),
+        );
+
+        let mut entries = Column::new();
+        for dir in path_browser.get_dirs() {
+            let path = path_browser.get_current_dir().join(dir);
+            entries = entries.push(
+                Button::new(Text::new(format!("{}/", dir)).size(TEXT_SIZE))
+                    .on_press(MainLayoutMessage::PathBrowserDirOpened(
+                        path.to_string_lossy().to_string(),
+                    ))
+                    .style(style::Button::Inactive)
+                    .width(Length::Fill),
+            );
+        }
+        for file in path_browser.get_files() {
+            let path = path_browser.get_current_dir().join(file);
+            entries = entries.push(
+                Button::new(Text::new(file).size(TEXT_SIZE))
+                    .on_press(MainLayoutMessage::PathPicked(
+                        target,
+                        Some(path.to_string_lossy().to_string()),
+                    ))
+                    .width(Length::Fill),
+            );
+        }
+
+        let mut column = Column::new()
+            .push(
+                Text::new(if path_browser.is_dir_selection() {
+                    "Select a directory"
+                } else {
+                    "Select a file"
+                })
+                .size(TEXT_SIZE),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(Text::new(path_browser.get_current_dir().to_string_lossy()).size(SMALL_TEXT_SIZE))
+            .spacing(ELEMENT_SPACING)
+            .push(buttons);
+        if let Some(error) = path_browser.get_error() {
+            column = column.push(
+                Text::new(error)
+                    .size(SMALL_TEXT_SIZE)
+                    .style(style::Text::Warning),
+            );
+        }
+
+        column
+            .spacing(ELEMENT_SPACING)
+            .push(Scrollable::new(entries.spacing(2)).height(Length::Fill))
+            .padding(10)
+            .into()
+    }
+
+    fn get_command_palette(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let commands = self.get_palette_commands();
+
+        let mut list = Column::new();
+        for command in self.command_palette.filter(&commands) {
+            let mut row =
+                Row::new().push(Text::new(&command.name).size(TEXT_SIZE).width(Length::Fill));
+            if let Some(shortcut) = command.shortcut {
+                row = row.spacing(ELEMENT_SPACING).push(
+                    Text::new(shortcut)
+                        .size(SMALL_TEXT_SIZE)
+                        .style(style::Text::Warning),
+                );
+            }
+
+            list = list.push(
+                Button::new(row)
+                    .on_press(MainLayoutMessage::CommandPaletteCommandSelected(Box::new(
+                        command.message.clone(),
+                    )))
+                    .style(style::Button::Inactive)
+                    .width(Length::Fill),
+            );
+        }
+
+        Column::new()
+            .push(
+                Row::new()
+                    .push(
+                        TextInput::new(
+                            "Type to search commands, press Enter to run the first one...",
+                            self.command_palette.get_query(),
+                            MainLayoutMessage::CommandPaletteQueryChanged,
+                        )
+                        .on_submit(MainLayoutMessage::CommandPaletteSubmitted)
+                        .padding(TEXT_INPUT_PADDING)
+                        .size(TEXT_SIZE)
+                        .width(Length::Fill),
+                    )
+                    .spacing(ELEMENT_SPACING)
+                    .push(
+                        Button::new(Text::new("Close (Esc)").size(TEXT_SIZE))
+                            .on_press(MainLayoutMessage::CommandPaletteClosed),
+                    ),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(Scrollable::new(list.spacing(2)).height(Length::Fill))
+            .padding(10)
+            .into()
+    }
+
+    fn get_toasts(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut toasts = Column::new();
+
+        for (index, toast) in self.notifications.get_toasts().iter().enumerate() {
+            let mut text = Text::new(&toast.text).size(SMALL_TEXT_SIZE);
+            match toast.kind {
+                NotificationKind::Success => text = text.style(style::Text::Added),
+                NotificationKind::Warning | NotificationKind::Error => {
+                    text = text.style(style::Text::Warning)
+                }
+                NotificationKind::Info => {}
+            }
+
+            toasts = toasts.push(
+                Container::new(
+                    Row::new()
+                        .push(text.width(Length::Fill))
+                        .spacing(ELEMENT_SPACING)
+                        .push(
+                            Button::new(Text::new("Close").size(SMALL_TEXT_SIZE))
+                                .on_press(MainLayoutMessage::ToastClosed(index)),
+                        ),
+                )
+                .padding(TEXT_INPUT_PADDING)
+                .width(Length::Fill)
+                .style(style::Container::Panel),
+            );
+        }
+
+        toasts.spacing(ELEMENT_SPACING).into()
+    }
+
+    fn get_swf_queue_section(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        if self.swf_queue.is_empty() {
+            return Column::new().into();
+        }
+
+        let file_names = self
+            .swf_queue
+            .iter()
+            .map(|path| {
+                path.file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        Container::new(
+            Row::new()
+                .push(
+                    Text::new(format!(
+                        "Queued .swf files (opened after generating): {}",
+                        file_names
+                    ))
+                    .size(SMALL_TEXT_SIZE)
+                    .width(Length::Fill),
+                )
+                .spacing(ELEMENT_SPACING)
+                .push(
+                    Button::new(Text::new("Open next").size(SMALL_TEXT_SIZE))
+                        .on_press(MainLayoutMessage::OpenNextQueuedSwfFile),
+                )
+                .spacing(ELEMENT_SPACING)
+                .push(
+                    Button::new(Text::new("Clear queue").size(SMALL_TEXT_SIZE))
+                        .on_press(MainLayoutMessage::SwfQueueCleared)
+                        .style(style::Button::Dangerous),
+                ),
+        )
+        .padding(TEXT_INPUT_PADDING)
+        .width(Length::Fill)
+        .style(style::Container::Panel)
+        .into()
+    }
+
+    fn get_toolbar(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut undo_button = Button::new(Text::new("Undo (Ctrl+Z)").size(TEXT_SIZE));
+        if self.undo_history.can_undo() {
+            undo_button = undo_button.on_press(MainLayoutMessage::UndoClicked);
+        }
+
+        let mut redo_button = Button::new(Text::new("Redo (Ctrl+Y)").size(TEXT_SIZE));
+        if self.undo_history.can_redo() {
+            redo_button = redo_button.on_press(MainLayoutMessage::RedoClicked);
+        }
+
+        Row::new()
+            .push(undo_button)
+            .spacing(ELEMENT_SPACING)
+            .push(redo_button)
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Button::new(Text::new("Commands (Ctrl+Shift+P)").size(TEXT_SIZE))
+                    .on_press(MainLayoutMessage::CommandPaletteOpened),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Checkbox::new(
+                    self.is_xml_preview_shown,
+                    "Show XML preview",
+                    MainLayoutMessage::XmlPreviewToggled,
+                )
+                .text_size(TEXT_SIZE),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Checkbox::new(
+                    self.is_alignment_preview_shown,
+                    "Show alignment preview",
+                    MainLayoutMessage::AlignmentPreviewToggled,
+                )
+                .text_size(TEXT_SIZE),
+            )
+            .into()
+    }
+
+    fn get_alignment_preview(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        if !self.is_alignment_preview_shown {
+            return Column::new().into();
+        }
+
+        // Prefer the size of the element, then the stage size.
+        let element_size = if self.is_size_used() && self.width != 0 && self.height != 0 {
+            Some((self.width, self.height))
+        } else {
+            self.swf_info
+                .as_ref()
+                .map(|swf_info| (swf_info.stage_width, swf_info.stage_height))
+        };
+
+        let preview = AlignmentPreview {
+            screen: self.preview_resolution,
+            element_size,
+            align_mode: self.align_mode.unwrap_or_default(),
+            halign: self.halign.unwrap_or_default(),
+            valign: self.valign.unwrap_or_default(),
+            scale: self.scale,
+            maximize: self.maximize,
+        };
+
+        Column::new()
+            .push(
+                Row::new()
+                    .push(
+                        Text::new("Screen resolution:")
+                            .size(TEXT_SIZE)
+                            .vertical_alignment(Vertical::Center),
+                    )
+                    .spacing(ELEMENT_SPACING)
+                    .push(
+                        PickList::new(
+                            &ScreenResolution::ALL[..],
+                            Some(self.preview_resolution),
+                            MainLayoutMessage::PreviewResolutionSelected,
+                        )
+                        .text_size(TEXT_SIZE),
+                    ),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Canvas::new(preview)
+                    .width(Length::Fill)
+                    .height(Length::Units(ALIGNMENT_PREVIEW_HEIGHT)),
+            )
+            .into()
+    }
+
+    fn get_xml_preview(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut lines = Column::new();
+        for line in self.xml_preview.iter() {
+            lines = lines.push(match line {
+                DiffLine::Unchanged(line) => Text::new(format!("  {}", line)).size(SMALL_TEXT_SIZE),
+                DiffLine::Added(line) => Text::new(format!("+ {}", line))
+                    .size(SMALL_TEXT_SIZE)
+                    .style(style::Text::Added),
+                DiffLine::Removed(line) => Text::new(format!("- {}", line))
+                    .size(SMALL_TEXT_SIZE)
+                    .style(style::Text::Removed),
+            });
+        }
+
+        let mut wider_button = Button::new(Text::new("<").size(TEXT_SIZE));
+        if self.xml_preview_split < XML_PREVIEW_SPLIT_RANGE.1 {
+            wider_button = wider_button.on_press(MainLayoutMessage::XmlPreviewSplitChanged(
+                self.xml_preview_split + XML_PREVIEW_SPLIT_STEP,
+            ));
+        }
+        let mut narrower_button = Button::new(Text::new(">").size(TEXT_SIZE));
+        if self.xml_preview_split > XML_PREVIEW_SPLIT_RANGE.0 {
+            narrower_button = narrower_button.on_press(MainLayoutMessage::XmlPreviewSplitChanged(
+                self.xml_preview_split - XML_PREVIEW_SPLIT_STEP,
+            ));
+        }
+
+        Column::new()
+            .push(
+                Row::new()
+                    .push(wider_button)
+                    .spacing(ELEMENT_SPACING)
+                    .push(narrower_button)
+                    .spacing(ELEMENT_SPACING)
+                    .push(Text::new(&self.xml_preview_title).size(TEXT_SIZE)),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(Scrollable::new(lines).height(Length::Fill))
+            .width(Length::FillPortion(self.xml_preview_split))
+            .into()
+    }
+
+    fn get_size_section(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut row = Row::new();
+
+        // Scale and maximize are not used in fixed mode.
+        if self.align_mode != Some(AlignMode::Fixed) {
+            row = row
+                .push(
+                    Checkbox::new(self.scale, "Scale", MainLayoutMessage::ScaleChanged)
+                        .text_size(TEXT_SIZE),
+                )
+                .spacing(ELEMENT_SPACING)
+                .push(
+                    Checkbox::new(
+                        self.maximize,
+                        "Maximize",
+                        MainLayoutMessage::MaximizeChanged,
+                    )
+                    .text_size(TEXT_SIZE),
+                )
+                .spacing(ELEMENT_SPACING);
+        }
+
+        if !self.is_size_used() {
+            return row.into();
+        }
+
+        row = row
+            .push(
+                Text::new("Width")
+                    .size(TEXT_SIZE)
+                    .vertical_alignment(Vertical::Center),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                TextInput::new(
+                    "",
+                    &self.width.to_string(),
+                    MainLayoutMessage::WidthTextChanged,
+                )
+                .padding(TEXT_INPUT_PADDING)
+                .size(TEXT_SIZE),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Text::new("Height")
+                    .size(TEXT_SIZE)
+                    .vertical_alignment(Vertical::Center),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                TextInput::new(
+                    "",
+                    &self.height.to_string(),
+                    MainLayoutMessage::HeightTextChanged,
+                )
+                .padding(TEXT_INPUT_PADDING)
+                .size(TEXT_SIZE),
+            );
+
+        if let Some(swf_info) = &self.swf_info {
+            row = row.spacing(ELEMENT_SPACING).push(
+                Button::new(
+                    Text::new(format!(
+                        "Use stage size ({}x{})",
+                        swf_info.stage_width, swf_info.stage_height
+                    ))
+                    .size(TEXT_SIZE),
+                )
+                .on_press(MainLayoutMessage::UseStageSizeClicked),
+            );
+        }
+
+        if self.get_stage_size_mismatch().is_some() {
+            row = row.spacing(ELEMENT_SPACING).push(
+                Text::new("Differs from the SWF stage size")
+                    .size(SMALL_TEXT_SIZE)
+                    .style(style::Text::Warning)
+                    .vertical_alignment(Vertical::Center),
+            );
+        }
+
+        row.into()
+    }
+
+    /// Returns title of the SWF report tab (with the number of warnings if there are any).
+    fn get_swf_report_title(&self) -> String {
+        let mut warning_count = match &self.swf_info {
+            Some(swf_info) => swf_info
+                .get_unknown_fonts(&ApplicationConfig::parse_font_list(&self.project_fonts))
+                .len(),
+            None => 0,
+        };
+        if let Some(gfx_summary) = &self.gfx_summary {
+            warning_count += gfx_summary.problems.len();
+        }
+
+        if warning_count == 0 {
+            String::from("SWF report")
+        } else {
+            format!("SWF report ({} warnings)", warning_count)
+        }
+    }
+
+    fn get_swf_report(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut report = Column::new().spacing(ELEMENT_SPACING);
+
+        if self.swf_info.is_none() {
+            report =
+                report.push(Text::new("Select a .swf file to see the report.").size(TEXT_SIZE));
+            return Scrollable::new(report).height(Length::Fill).into();
+        }
+        let swf_info = self.swf_info.as_ref().unwrap();
+
+        report = report
+            .push(
+                Text::new(format!(
+                    "Stage size: {}x{}",
+                    swf_info.stage_width, swf_info.stage_height
+                ))
+                .size(TEXT_SIZE),
+            )
+            .push(Text::new("Fonts:").size(TEXT_SIZE));
+
+        if swf_info.fonts.is_empty() {
+            report = report.push(Text::new("No fonts are used.").size(SMALL_TEXT_SIZE));
+        }
+
+        let project_fonts = ApplicationConfig::parse_font_list(&self.project_fonts);
+        for font in swf_info.fonts.iter() {
+            if !project_fonts.is_empty() && !font.is_in_list(&project_fonts) {
+                report = report.push(
+                    Text::new(format!("{} - not a project font", font))
+                        .size(SMALL_TEXT_SIZE)
+                        .style(style::Text::Warning),
+                );
+            } else {
+                report = report.push(Text::new(font.to_string()).size(SMALL_TEXT_SIZE));
+            }
+        }
+
+        report = report.push(Text::new("Images:").size(TEXT_SIZE));
+        let swf_file_name = Path::new(&self.path_to_swf_file)
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+        for line in GfxExportManager::get_image_report(
+            &swf_file_name,
+            &swf_info.images,
+            &self.additional_gfxexport_args,
+        ) {
+            report = report.push(Text::new(line).size(SMALL_TEXT_SIZE));
+        }
+
+        if let Some(gfx_summary) = &self.gfx_summary {
+            report = report.push(Text::new("Generated .gfx file:").size(TEXT_SIZE));
+            for problem in gfx_summary.problems.iter() {
+                report = report.push(
+                    Text::new(problem)
+                        .size(SMALL_TEXT_SIZE)
+                        .style(style::Text::Warning),
+                );
+            }
+            for line in gfx_summary.get_report() {
+                report = report.push(Text::new(line).size(SMALL_TEXT_SIZE));
+            }
+        }
+
+        Scrollable::new(report).height(Length::Fill).into()
+    }
+
+    fn get_entity_list(&self) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut list = Column::new();
+
+        // Get reference to vector to use.
+        let mut _vec_to_use = &self.functions;
+        match self.current_list {
+            EntityList::Functions => {
+                _vec_to_use = &self.functions;
+            }
+            EntityList::Events => {
+                _vec_to_use = &self.events;
+            }
+        }
+
+        // Fill list.
+        let filter = self.entity_list_filter.trim().to_lowercase();
+        let mut shown_item_count = 0;
+        for (index, item) in _vec_to_use.iter().enumerate() {
+            if !item.is_matching_filter(&filter) {
+                continue;
+            }
+            shown_item_count += 1;
+
+            if self.are_parameters_collapsed {
+                list = list.push(self.get_collapsed_list_item(_vec_to_use, index, &filter));
+                continue;
+            }
+
+            // Collect parameters.
+            let mut params = Column::new();
+            for (param_index, parameter) in item.parameters.iter().enumerate() {
+                params = params.push(
+                            Row::new()
+                                    .push(PickList::new(
+                                &ParameterType::ALL[..],
+                                parameter.type_,
+                            move |type_: ParameterType| -> MainLayoutMessage {
+                                    MainLayoutMessage::EntityListParameterTypeChanged(index, param_index, type_)
+                                },
                                 ))
                                 .spacing(ELEMENT_SPACING)
                                 .push(
@@ -539,8 +2563,13 @@ impl MainLayout {
                                             },
                                         )
                                         .size(TEXT_SIZE)
+                                        .style(match item.get_parameter_name_error(param_index) {
+                                            Some(_) => style::TextInput::Error,
+                                            None => Self::get_filtered_input_style(&parameter.name, &filter, style::TextInput::Default),
+                                        })
                                         .padding(TEXT_INPUT_PADDING),
                                     )
+                                    .push(Self::get_name_error_text(item.get_parameter_name_error(param_index)))
                                     .spacing(ELEMENT_SPACING)
                                     .push(
                                         TextInput::new(
@@ -555,15 +2584,27 @@ impl MainLayout {
                                             },
                                         )
                                         .size(TEXT_SIZE)
+                                        .style(Self::get_filtered_input_style(&parameter.description, &filter, style::TextInput::Default))
                                         .padding(TEXT_INPUT_PADDING),
                                     )
                                     .width(Length::FillPortion(LIST_ITEM_PORTION)),
                                 )
                                 .spacing(ELEMENT_SPACING)
                                 .push(
-                                    Button::new(Text::new("Remove parameter").size(TEXT_SIZE))
-                                        .on_press(MainLayoutMessage::EntityListRemoveParameter(index, param_index))
-                                        .style(style::Button::Dangerous)
+                                    Column::new()
+                                        .push(Self::get_move_buttons(
+                                            param_index,
+                                            item.parameters.len(),
+                                            MainLayoutMessage::EntityListMoveParameterUp(index, param_index),
+                                            MainLayoutMessage::EntityListMoveParameterDown(index, param_index),
+                                        ))
+                                        .spacing(ELEMENT_SPACING)
+                                        .push(
+                                            Button::new(Text::new("Remove parameter").size(TEXT_SIZE))
+                                                .on_press(MainLayoutMessage::EntityListRemoveParameter(index, param_index))
+                                                .style(style::Button::Dangerous)
+                                                .width(Length::Fill),
+                                        )
                                         .width(Length::FillPortion(REMOVE_BUTTON_PORTION)),
                                 ),
                         );
@@ -594,20 +2635,26 @@ impl MainLayout {
                                     },
                                 )
                                 .size(TEXT_SIZE)
-                                .style(style::TextInput::Special)
+                                .style(match UiRunnable::get_name_error(_vec_to_use, index) {
+                                    Some(_) => style::TextInput::Error,
+                                    None => Self::get_filtered_input_style(
+                                        &item.name,
+                                        &filter,
+                                        style::TextInput::Special,
+                                    ),
+                                })
                                 .padding(TEXT_INPUT_PADDING),
                             )
+                            .push(Self::get_name_error_text(UiRunnable::get_name_error(
+                                _vec_to_use,
+                                index,
+                            )))
                             .spacing(ELEMENT_SPACING)
                             .push(params)
                             .width(Length::FillPortion(LIST_ITEM_PORTION)),
                     )
                     .spacing(ELEMENT_SPACING)
-                    .push(
-                        Button::new(Text::new("Remove item").size(TEXT_SIZE))
-                            .on_press(MainLayoutMessage::EntityListRemoveItem(index))
-                            .style(style::Button::Dangerous)
-                            .width(Length::FillPortion(REMOVE_BUTTON_PORTION)),
-                    ),
+                    .push(self.get_list_item_actions(index, _vec_to_use.len(), !filter.is_empty())),
             );
         }
 
@@ -622,7 +2669,304 @@ impl MainLayout {
             .width(Length::Fill),
         );
 
-        Scrollable::new(list).height(Length::Fill).into()
+        Column::new()
+            .push(self.get_entity_list_filter(shown_item_count, _vec_to_use.len()))
+            .spacing(ELEMENT_SPACING)
+            .push(Scrollable::new(list).height(Length::Fill))
+            .into()
+    }
+
+    /// Returns the filter box and the "collapse all parameters" toggle.
+    ///
+    /// ## Arguments
+    /// * `shown_item_count`: number of items that match the filter.
+    /// * `item_count`: number of items in the list.
+    fn get_entity_list_filter(
+        &self,
+        shown_item_count: usize,
+        item_count: usize,
+    ) -> Element<MainLayoutMessage, Renderer<Theme>> {
+        let mut row = Row::new()
+            .push(
+                TextInput::new(
+                    "Filter by name, parameter name or description...",
+                    &self.entity_list_filter,
+                    MainLayoutMessage::EntityListFilterChanged,
+                )
+                .size(TEXT_SIZE)
+                .padding(TEXT_INPUT_PADDING)
+                .width(Length::Fill),
+            )
+            .spacing(ELEMENT_SPACING);
+
+        if !self.entity_list_filter.is_empty() {
+            row = row
+                .push(
+                    Text::new(format!("{} of {} shown", shown_item_count, item_count))
+                        .size(SMALL_TEXT_SIZE)
+                        .vertical_alignment(Vertical::Center),
+                )
+                .spacing(ELEMENT_SPACING)
+                .push(
+                    Button::new(Text::new("Clear").size(TEXT_SIZE))
+                        .on_press(MainLayoutMessage::EntityListFilterChanged(String::new())),
+                )
+                .spacing(ELEMENT_SPACING);
+        }
+
+        row.push(
+            Checkbox::new(
+                self.are_parameters_collapsed,
+                "Collapse all parameters",
+                MainLayoutMessage::EntityListParametersCollapsed,
+            )
+            .text_size(TEXT_SIZE),
+        )
+        .into()
+    }
+
+    /// Returns the item of the list as a single line (parameters are only listed).
+    ///
+    /// ## Arguments
+    /// * `items`: list of functions or events.
+    /// * `index`: index of the item to show.
+    /// * `filter`: text to highlight (case insensitive).
+    fn get_collapsed_list_item<'a>(
+        &self,
+        items: &'a [UiRunnable],
+        index: usize,
+        filter: &str,
+    ) -> Element<'a, MainLayoutMessage, Renderer<Theme>> {
+        let item = &items[index];
+
+        let mut parameters = Row::new().push(
+            Text::new(format!("{} parameter(s): ", item.parameters.len())).size(SMALL_TEXT_SIZE),
+        );
+        for (param_index, parameter) in item.parameters.iter().enumerate() {
+            if param_index != 0 {
+                parameters = parameters.push(Text::new(", ").size(SMALL_TEXT_SIZE));
+            }
+            parameters = parameters.push(Self::get_highlighted_text(&parameter.name, filter));
+            if !filter.is_empty() && is_text_matching_filter(&parameter.description, filter) {
+                parameters = parameters
+                    .push(Text::new(" (").size(SMALL_TEXT_SIZE))
+                    .push(Self::get_highlighted_text(&parameter.description, filter))
+                    .push(Text::new(")").size(SMALL_TEXT_SIZE));
+            }
+        }
+
+        Row::new()
+            .push(
+                Column::new()
+                    .push(
+                        TextInput::new("", &item.name, move |name: String| -> MainLayoutMessage {
+                            MainLayoutMessage::EntityListItemChanged(index, name)
+                        })
+                        .size(TEXT_SIZE)
+                        .style(match UiRunnable::get_name_error(items, index) {
+                            Some(_) => style::TextInput::Error,
+                            None => Self::get_filtered_input_style(
+                                &item.name,
+                                filter,
+                                style::TextInput::Special,
+                            ),
+                        })
+                        .padding(TEXT_INPUT_PADDING),
+                    )
+                    .push(parameters)
+                    .width(Length::FillPortion(LIST_ITEM_PORTION)),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(self.get_list_item_actions(index, items.len(), !filter.is_empty()))
+            .into()
+    }
+
+    /// Returns buttons to move, duplicate and remove an item of the list.
+    ///
+    /// ## Arguments
+    /// * `index`: index of the item in the list.
+    /// * `count`: number of items in the list.
+    /// * `is_filtered`: whether some items may be hidden by the filter.
+    fn get_list_item_actions<'a>(
+        &self,
+        index: usize,
+        count: usize,
+        is_filtered: bool,
+    ) -> Element<'a, MainLayoutMessage, Renderer<Theme>> {
+        // Neighbours of a filtered item may be hidden, moving it would look like it did nothing.
+        let (move_index, move_count) = if is_filtered { (0, 0) } else { (index, count) };
+
+        Column::new()
+            .push(Self::get_move_buttons(
+                move_index,
+                move_count,
+                MainLayoutMessage::EntityListMoveItemUp(index),
+                MainLayoutMessage::EntityListMoveItemDown(index),
+            ))
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Button::new(Text::new("Duplicate").size(TEXT_SIZE))
+                    .on_press(MainLayoutMessage::EntityListDuplicateItem(index))
+                    .width(Length::Fill),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Button::new(
+                    Text::new(match self.current_list {
+                        EntityList::Functions => "Move to events",
+                        EntityList::Events => "Move to functions",
+                    })
+                    .size(TEXT_SIZE),
+                )
+                .on_press(MainLayoutMessage::EntityListMoveItemToOtherList(index))
+                .width(Length::Fill),
+            )
+            .spacing(ELEMENT_SPACING)
+            .push(
+                Button::new(Text::new("Remove item").size(TEXT_SIZE))
+                    .on_press(MainLayoutMessage::EntityListRemoveItem(index))
+                    .style(style::Button::Dangerous)
+                    .width(Length::Fill),
+            )
+            .width(Length::FillPortion(REMOVE_BUTTON_PORTION))
+            .into()
+    }
+
+    /// Returns the style of a text input that is highlighted if its value matches the filter.
+    ///
+    /// ## Arguments
+    /// * `value`: value of the text input.
+    /// * `filter`: text to find (case insensitive).
+    /// * `default_style`: style to use if the filter is empty or does not match.
+    fn get_filtered_input_style(
+        value: &str,
+        filter: &str,
+        default_style: style::TextInput,
+    ) -> style::TextInput {
+        if !filter.is_empty() && is_text_matching_filter(value, filter) {
+            style::TextInput::Highlighted
+        } else {
+            default_style
+        }
+    }
+
+    /// Returns the text with all occurrences of the filter highlighted.
+    ///
+    /// ## Arguments
+    /// * `text`: text to show.
+    /// * `filter`: text to highlight (case insensitive).
+    fn get_highlighted_text<'a>(
+        text: &str,
+        filter: &str,
+    ) -> Element<'a, MainLayoutMessage, Renderer<Theme>> {
+        let lowercase_text = text.to_lowercase();
+        let filter = filter.to_lowercase();
+
+        // Byte offsets of the lowercase text only match the original text
+        // if lowercasing did not change lengths of characters.
+        if filter.is_empty() || lowercase_text.len() != text.len() {
+            return Text::new(text.to_string()).size(SMALL_TEXT_SIZE).into();
+        }
+
+        let mut row = Row::new();
+        let mut last_end = 0;
+        for (start, _) in lowercase_text.match_indices(&filter) {
+            if start > last_end {
+                row = row.push(Text::new(text[last_end..start].to_string()).size(SMALL_TEXT_SIZE));
+            }
+            row = row.push(
+                Text::new(text[start..start + filter.len()].to_string())
+                    .size(SMALL_TEXT_SIZE)
+                    .style(style::Text::Highlighted),
+            );
+            last_end = start + filter.len();
+        }
+        if last_end < text.len() {
+            row = row.push(Text::new(text[last_end..].to_string()).size(SMALL_TEXT_SIZE));
+        }
+
+        row.into()
+    }
+
+    /// Returns the text that describes a problem with the name (empty if there is no problem).
+    fn get_name_error_text<'a>(
+        error: Option<String>,
+    ) -> Element<'a, MainLayoutMessage, Renderer<Theme>> {
+        match error {
+            Some(error) => Text::new(error)
+                .size(SMALL_TEXT_SIZE)
+                .style(style::Text::Warning)
+                .into(),
+            None => Column::new().into(),
+        }
+    }
+
+    /// Returns problems with names of functions, events and their parameters.
+    fn get_entity_list_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        for (list_name, items) in [("function", &self.functions), ("event", &self.events)] {
+            for (index, item) in items.iter().enumerate() {
+                if let Some(error) = UiRunnable::get_name_error(items, index) {
+                    errors.push(format!("{} #{}: {}", list_name, index + 1, error));
+                }
+
+                for param_index in 0..item.parameters.len() {
+                    if let Some(error) = item.get_parameter_name_error(param_index) {
+                        errors.push(format!(
+                            "{} \"{}\", parameter #{}: {}",
+                            list_name,
+                            item.name,
+                            param_index + 1,
+                            error
+                        ));
+                    }
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Returns "Up" and "Down" buttons for an entry of the list, buttons that can't move
+    /// the entry (the first or the last entry) are disabled.
+    ///
+    /// ## Arguments
+    /// * `index`: index of the entry in the list.
+    /// * `count`: number of entries in the list.
+    /// * `up_message`: message to send when "Up" is pressed.
+    /// * `down_message`: message to send when "Down" is pressed.
+    fn get_move_buttons<'a>(
+        index: usize,
+        count: usize,
+        up_message: MainLayoutMessage,
+        down_message: MainLayoutMessage,
+    ) -> Element<'a, MainLayoutMessage, Renderer<Theme>> {
+        let mut up_button = Button::new(
+            Text::new("Up")
+                .size(TEXT_SIZE)
+                .horizontal_alignment(Horizontal::Center),
+        )
+        .width(Length::Fill);
+        if index > 0 {
+            up_button = up_button.on_press(up_message);
+        }
+
+        let mut down_button = Button::new(
+            Text::new("Down")
+                .size(TEXT_SIZE)
+                .horizontal_alignment(Horizontal::Center),
+        )
+        .width(Length::Fill);
+        if index + 1 < count {
+            down_button = down_button.on_press(down_message);
+        }
+
+        Row::new()
+            .push(up_button)
+            .spacing(ELEMENT_SPACING)
+            .push(down_button)
+            .into()
     }
 
     /// Check whether the specified directory contains an XML file with
@@ -650,81 +2994,82 @@ impl MainLayout {
         Ok(path.to_string_lossy().to_string())
     }
 
-    fn are_all_required_fields_filled(&self) -> bool {
+    fn are_all_required_fields_filled(&mut self) -> bool {
         if self.path_to_gfxexport_bin.is_empty() {
-            Self::show_message_about_empty_field("Path to GFxExport");
+            self.show_message_about_empty_field("Path to GFxExport");
             return false;
         }
 
         if self.path_to_swf_file.is_empty() {
-            Self::show_message_about_empty_field("Path to .swf file");
+            self.show_message_about_empty_field("Path to .swf file");
             return false;
         }
 
         if self.path_to_gfx_dir.is_empty() {
-            Self::show_message_about_empty_field("Output directory for .gfx files");
+            self.show_message_about_empty_field("Output directory for .gfx files");
             return false;
         }
 
         if self.path_to_xml_dir.is_empty() {
-            Self::show_message_about_empty_field("Output directory for .xml files");
+            self.show_message_about_empty_field("Output directory for .xml files");
             return false;
         }
 
         if self.ui_elements_name.is_empty() {
-            Self::show_message_about_empty_field("Elements name");
+            self.show_message_about_empty_field("Elements name");
             return false;
         }
 
         if self.ui_element_name.is_empty() {
-            Self::show_message_about_empty_field("Element name");
+            self.show_message_about_empty_field("Element name");
             return false;
         }
 
         true
     }
 
-    fn show_message_about_empty_field(field_name: &str) {
-        MessageDialog::new()
-            .set_type(MessageType::Error)
-            .set_title("Error")
-            .set_text(&format!("Field \"{}\" must be filled.", field_name))
-            .show_alert()
-            .unwrap();
+    fn show_message_about_empty_field(&mut self, field_name: &str) {
+        self.notifications.show_message(
+            NotificationKind::Error,
+            format!("Field \"{}\" must be filled.", field_name),
+        );
     }
 
-    fn get_data_from_existing_xml(&mut self, path_to_xml_file: &str) {
+    /// Fills fields with values from the existing `.xml` file.
+    ///
+    /// ## Arguments
+    /// * `path_to_xml_file`: path to the `.xml` file.
+    /// * `should_ask`: whether to ask the user before reading the file.
+    fn get_data_from_existing_xml(&mut self, path_to_xml_file: &str, should_ask: bool) {
         if !Path::new(path_to_xml_file).exists() {
             return;
         }
 
         // Ask if the user wants to read this file.
-        let yes = MessageDialog::new()
-            .set_type(MessageType::Info)
-            .set_title("Info")
-            .set_text(&format!(
-                "The XML file \"{}\" already exists, do you want \
+        if should_ask {
+            self.notifications.ask(
+                NotificationKind::Info,
+                format!(
+                    "The XML file \"{}\" already exists, do you want \
                         to get data from this file here?",
-                &path_to_xml_file
-            ))
-            .show_confirm()
-            .unwrap();
-        if !yes {
+                    &path_to_xml_file
+                ),
+                MainLayoutMessage::LoadExistingXmlConfirmed(path_to_xml_file.to_string()),
+                None,
+            );
             return;
         }
 
         // Parse XML file.
         let result = XmlManager::read_config(path_to_xml_file);
         if let Err(app_error) = result {
-            MessageDialog::new()
-                .set_type(MessageType::Error)
-                .set_title("Error")
-                .set_text(&format!(
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!(
                     "Failed to parse XML file at \"{}\". Error: {}",
                     path_to_xml_file, app_error
-                ))
-                .show_alert()
-                .unwrap();
+                ),
+            );
             return;
         }
         let config = result.unwrap();
@@ -732,49 +3077,110 @@ impl MainLayout {
         // Update values from config.
         self.ui_elements_name = config.ui_elements_name;
         self.ui_element_name = config.ui_element_name;
-        self.fullscreen = config.fullscreen;
+        self.align_mode = Some(config.align_mode);
+        self.scale = config.scale;
+        self.maximize = config.maximize;
+        self.width = config.width;
+        self.height = config.height;
         self.gfx_layer = config.gfx_layer;
         self.halign = Some(config.halign);
         self.valign = Some(config.valign);
         self.functions = config.functions;
         self.events = config.events;
+
+        // Old files don't specify the size, so take it from the movie.
+        if self.width == 0 && self.height == 0 {
+            self.use_stage_size();
+        }
+
+        // Make sure the element size matches the movie.
+        if let Some((stage_width, stage_height)) = self.get_stage_size_mismatch() {
+            self.notifications.show_message(
+                NotificationKind::Warning,
+                format!(
+                    "The XML file \"{}\" specifies element size {}x{} \
+                    but the stage size of the SWF movie is {}x{}.",
+                    path_to_xml_file, self.width, self.height, stage_width, stage_height
+                ),
+            );
+        }
     }
 
-    fn generate(&mut self, app_config: &mut ApplicationConfig) {
+    /// Generates .xml and .gfx files.
+    ///
+    /// ## Arguments
+    /// * `is_invalid_names_confirmed`: whether the user agreed to generate files
+    ///   with invalid names of functions, events or parameters.
+    /// * `is_overwrite_confirmed`: whether the user agreed to overwrite the existing .xml file.
+    fn generate(
+        &mut self,
+        app_config: &mut ApplicationConfig,
+        is_invalid_names_confirmed: bool,
+        is_overwrite_confirmed: bool,
+    ) {
         if !self.are_all_required_fields_filled() {
             return;
         }
 
-        // Save additional GFxExport arguments to config.
-        app_config.additional_gfxexport_args = self.additional_gfxexport_args.clone();
+        // Save GFxExport profile and project fonts to config.
+        self.store_profile(app_config);
+        if let Some(swf_dir) = Path::new(&self.path_to_swf_file).parent() {
+            app_config.set_last_used_profile(&swf_dir.to_string_lossy(), &self.profile_name);
+        }
+        app_config.set_project_fonts(&self.project_fonts);
         if let Err(app_error) = app_config.save() {
-            MessageDialog::new()
-                .set_type(MessageType::Error)
-                .set_title("Error")
-                .set_text(&format!(
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!(
                     "Failed to save configuration file to {}.\n\nError: {}",
-                    ApplicationConfig::get_config_file_path().to_string_lossy(),
+                    app_config.get_config_file_path(),
                     app_error
-                ))
-                .show_alert()
-                .unwrap();
+                ),
+            );
         }
 
         // Check .swf file name.
         if Path::new(&self.path_to_swf_file).file_stem().is_none() {
-            MessageDialog::new()
-                .set_type(MessageType::Error)
-                .set_title("Error")
-                .set_text("*.swf file should have a file name.")
-                .show_alert()
-                .unwrap();
+            self.notifications.show_message(
+                NotificationKind::Error,
+                String::from("*.swf file should have a file name."),
+            );
+            return;
+        }
+
+        // Check names of functions, events and parameters.
+        let errors = self.get_entity_list_errors();
+        if !errors.is_empty() && !is_invalid_names_confirmed {
+            self.notifications.ask(
+                NotificationKind::Warning,
+                format!(
+                    "Some names can't be used by CRYENGINE (the element will not work \
+                    as expected):\n\n{}\n\nDo you want to generate files anyway?",
+                    errors.join("\n")
+                ),
+                MainLayoutMessage::GenerateConfirmed(true, is_overwrite_confirmed),
+                None,
+            );
+            return;
+        }
+
+        // Check output directory templates.
+        if let Some(error) = &self.output_dirs_error {
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!(
+                    "Failed to determine output directories.\n\nError: {}",
+                    error
+                ),
+            );
             return;
         }
 
         let file_name = Path::new(&self.path_to_swf_file)
             .file_stem()
             .unwrap()
-            .to_string_lossy();
+            .to_string_lossy()
+            .to_string();
 
         // Construct path to output .xml file.
         let mut path_to_xml_file = Path::new(&self.path_to_xml_dir).to_path_buf();
@@ -785,126 +3191,260 @@ impl MainLayout {
         path_to_gfx_file.push(format!("{}.gfx", file_name));
 
         // Check if .xml file already exists.
-        if path_to_xml_file.exists() {
-            let yes = MessageDialog::new()
-                .set_type(MessageType::Warning)
-                .set_title("Warning")
-                .set_text(&format!(
+        if path_to_xml_file.exists() && !is_overwrite_confirmed {
+            self.notifications.ask(
+                NotificationKind::Warning,
+                format!(
                     "Output .xml file \"{}\" already exists, do you want to overwrite it?",
                     path_to_xml_file.to_string_lossy()
-                ))
-                .show_confirm()
-                .unwrap();
-            if !yes {
-                return;
-            }
+                ),
+                MainLayoutMessage::GenerateConfirmed(is_invalid_names_confirmed, true),
+                None,
+            );
+            return;
         }
 
         // Make sure output directories exist.
         if !Path::new(&self.path_to_xml_dir).exists() {
             if let Err(e) = fs::create_dir_all(&self.path_to_xml_dir) {
-                MessageDialog::new()
-                    .set_type(MessageType::Error)
-                    .set_title("Error")
-                    .set_text(&format!(
+                self.notifications.show_message(
+                    NotificationKind::Error,
+                    format!(
                         "Failed to create output directory for .xml files, error: {}",
                         e
-                    ))
-                    .show_alert()
-                    .unwrap();
+                    ),
+                );
                 return;
             }
         }
         if !Path::new(&self.path_to_gfx_dir).exists() {
             if let Err(e) = fs::create_dir_all(&self.path_to_gfx_dir) {
-                MessageDialog::new()
-                    .set_type(MessageType::Error)
-                    .set_title("Error")
-                    .set_text(&format!(
+                self.notifications.show_message(
+                    NotificationKind::Error,
+                    format!(
                         "Failed to create output directory for .gfx files, error: {}",
                         e
-                    ))
-                    .show_alert()
-                    .unwrap();
+                    ),
+                );
                 return;
             }
         }
 
         // Construct config.
-        let config = XmlConfig {
-            ui_elements_name: self.ui_elements_name.clone(),
-            ui_element_name: self.ui_element_name.clone(),
-            gfx_file_name: format!("{}.gfx", file_name),
-            gfx_layer: self.gfx_layer,
-            fullscreen: self.fullscreen,
-            halign: self.halign.unwrap_or(HAlign::Center),
-            valign: self.valign.unwrap_or(VAlign::Center),
-            functions: self.functions.clone(),
-            events: self.events.clone(),
-        };
+        let config = self.get_xml_config(format!("{}.gfx", file_name));
 
         // Write to file.
         if let Err(app_error) = XmlManager::write_config(
             config,
             path_to_xml_file.to_string_lossy().to_string().as_str(),
         ) {
-            MessageDialog::new()
-                .set_type(MessageType::Error)
-                .set_title("Error")
-                .set_text(&format!("Failed to write .xml file, error: {}", app_error))
-                .show_alert()
-                .unwrap();
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!("Failed to write .xml file, error: {}", app_error),
+            );
             return;
         }
 
-        // Split GFxExport additional arguments.
-        let mut args = self
-            .additional_gfxexport_args
-            .split_ascii_whitespace()
-            .collect::<Vec<&str>>();
-        args.push("-d"); // specify output directory
-        args.push(&self.path_to_gfx_dir);
+        // The preview should compare with the new file.
+        self.existing_xml_cache = None;
 
         // Merge arguments into one string to show to user.
         let mut args_to_show = String::new();
-        for arg in args.iter() {
+        for arg in GfxExportManager::get_export_args(
+            &self.path_to_gfx_dir,
+            &self.additional_gfxexport_args,
+        )
+        .iter()
+        {
             args_to_show = format!("{}\"{}\" ", args_to_show, arg);
         }
 
-        // Run GFxExport.
-        if let Err(e) = process::Command::new(&self.path_to_gfxexport_bin)
-            .arg(&self.path_to_swf_file)
-            .args(args)
-            .output()
-        {
-            MessageDialog::new()
-                .set_type(MessageType::Error)
-                .set_title("Error")
-                .set_text(&format!("GFxExport failed, error: {}", e))
-                .show_alert()
-                .unwrap();
+        // Run GFxExport (if the .swf file or export options were changed).
+        self.gfx_summary = None;
+        let result = GfxExportManager::export(
+            &app_config.get_current_profile(),
+            &self.path_to_swf_file,
+            &self.path_to_gfx_dir,
+            self.swf_info
+                .as_ref()
+                .map(|swf_info| &swf_info.images[..])
+                .unwrap_or_default(),
+            self.force_export,
+        );
+        if let Err(app_error) = result {
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!("GFxExport failed: {}", app_error.get_message()),
+            );
+            return;
+        }
+        let result = result.unwrap();
+
+        // Changes are saved, autosaved state is no longer needed.
+        self.clear_session(app_config);
+
+        let mut gfx_message = format!(
+            "\n\nOutput .gfx file size: {}",
+            result.summary.get_size_description()
+        );
+        if result.is_skipped {
+            gfx_message += "\n\nGFxExport was not started because the .swf file, \
+                GFxExport binary and arguments were not changed since the last export \
+                (enable \"Force GFxExport\" to export anyway).";
+        }
+        let mut has_warnings = false;
+        for problem in result.summary.problems.iter().chain(result.warnings.iter()) {
+            gfx_message += &format!("\n\nWARNING: {}", problem);
+            has_warnings = true;
+        }
+        self.gfx_summary = Some(result.summary);
+
+        // Check that images were extracted.
+        let mut missing_images_message = String::new();
+        if let Some(swf_info) = &self.swf_info {
+            let missing_images = GfxExportManager::get_missing_image_files(
+                &self.path_to_gfx_dir,
+                &file_name,
+                &swf_info.images,
+                &self.additional_gfxexport_args,
+            );
+            if let Some(missing_images) = missing_images {
+                if !missing_images.is_empty() {
+                    has_warnings = true;
+                    missing_images_message = format!(
+                        "\n\nWARNING: the following image files were not found \
+                        in the output directory: {}",
+                        missing_images.join(", ")
+                    );
+                }
+            }
+        }
+
+        let report = format!(
+            "Successfully generated .gfx and .xml files.\n\n\
+            Output .xml file: {}\n\n\
+            Output .gfx file: {}\n\n\
+            Used GFxExport arguments: \"{}\" {}{}{}",
+            path_to_xml_file.to_string_lossy(),
+            path_to_gfx_file.to_string_lossy(),
+            &self.path_to_swf_file,
+            &args_to_show,
+            &gfx_message,
+            &missing_images_message
+        );
+        if has_warnings {
+            // Warnings should not disappear before the user reads them.
+            self.notifications
+                .show_message(NotificationKind::Warning, report);
+        } else {
+            self.notifications
+                .show_toast(NotificationKind::Success, report);
+        }
+
+        self.remember_recent_file(app_config);
+
+        // Continue with the next dropped file.
+        self.open_next_queued_swf_file(app_config);
+    }
+
+    fn update_additional_gfxexport_args(&mut self, args: String) {
+        self.additional_gfxexport_args = args;
+    }
+
+    fn update_project_fonts(&mut self, fonts: String) {
+        self.project_fonts = fonts;
+    }
+
+    fn update_force_export(&mut self, force: bool) {
+        self.force_export = force;
+    }
+
+    fn select_profile(&mut self, name: String, app_config: &mut ApplicationConfig) {
+        // Keep changes made to the previous profile.
+        self.store_profile(app_config);
+
+        let profile = app_config.get_profile(&name);
+        if profile.is_none() {
+            return;
+        }
+        let profile = profile.unwrap();
+        self.apply_profile(&profile);
+
+        // Use output directories of the selected profile.
+        self.update_output_dirs();
+
+        // Remember the profile for the directory of the selected .swf file.
+        match Path::new(&self.path_to_swf_file).parent() {
+            Some(swf_dir) if !self.path_to_swf_file.is_empty() => {
+                app_config.set_last_used_profile(&swf_dir.to_string_lossy(), &profile.name)
+            }
+            _ => app_config.current_profile = profile.name.clone(),
+        }
+        if let Err(app_error) = app_config.save() {
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!(
+                    "Failed to save configuration file to {}.\n\nError: {}",
+                    app_config.get_config_file_path(),
+                    app_error
+                ),
+            );
+        }
+    }
+
+    /// Shows values of the specified profile in the UI.
+    fn apply_profile(&mut self, profile: &GfxExportProfile) {
+        self.profile_name = profile.name.clone();
+        self.path_to_gfxexport_bin = profile.path_to_gfxexport_bin.clone();
+        self.additional_gfxexport_args = profile.additional_gfxexport_args.clone();
+        self.gfx_dir_template = profile.gfx_dir.clone();
+        self.xml_dir_template = profile.xml_dir.clone();
+    }
+
+    fn update_gfx_dir_template(&mut self, template: String) {
+        self.gfx_dir_template = template;
+        self.update_output_dirs();
+    }
+
+    fn update_xml_dir_template(&mut self, template: String) {
+        self.xml_dir_template = template;
+        self.update_output_dirs();
+    }
+
+    /// Resolves output directory templates for the selected .swf file.
+    fn update_output_dirs(&mut self) {
+        self.output_dirs_error = None;
+        if self.path_to_swf_file.is_empty() {
             return;
         }
 
-        MessageDialog::new()
-            .set_type(MessageType::Info)
-            .set_title("Info")
-            .set_text(&format!(
-                "Successfully generated .gfx and .xml files.\n\n\
-                Output .xml file: {}\n\n\
-                Output .gfx file: {}\n\n\
-                Used GFxExport arguments: \"{}\" {}",
-                path_to_xml_file.to_string_lossy(),
-                path_to_gfx_file.to_string_lossy(),
-                &self.path_to_swf_file,
-                &args_to_show
-            ))
-            .show_alert()
-            .unwrap();
+        let mut profile = GfxExportProfile::new(&self.profile_name);
+        profile.gfx_dir = self.gfx_dir_template.clone();
+        profile.xml_dir = self.xml_dir_template.clone();
+
+        let path_to_project_config = self.path_to_project_config.as_ref().map(Path::new);
+        match profile.get_output_dirs(
+            Path::new(&self.path_to_swf_file),
+            self.cry_project.as_ref(),
+            path_to_project_config,
+        ) {
+            Ok((path_to_gfx, path_to_xml)) => {
+                self.path_to_gfx_dir = path_to_gfx.to_string_lossy().to_string();
+                self.path_to_xml_dir = path_to_xml.to_string_lossy().to_string();
+            }
+            Err(app_error) => self.output_dirs_error = Some(app_error.get_message()),
+        }
     }
 
-    fn update_additional_gfxexport_args(&mut self, args: String) {
-        self.additional_gfxexport_args = args;
+    /// Saves values from the UI to the current profile (without saving the config file).
+    fn store_profile(&self, app_config: &mut ApplicationConfig) {
+        if let Some(mut profile) = app_config.get_profile(&self.profile_name) {
+            profile.path_to_gfxexport_bin = self.path_to_gfxexport_bin.clone();
+            profile.additional_gfxexport_args = self.additional_gfxexport_args.clone();
+            profile.gfx_dir = self.gfx_dir_template.clone();
+            profile.xml_dir = self.xml_dir_template.clone();
+            app_config.set_profile(&profile);
+        }
     }
 
     fn update_list_item(&mut self, index: usize, newname: String) {
@@ -977,9 +3517,23 @@ impl MainLayout {
         }
     }
 
+    /// Swaps the parameter with the previous (`up`) or the next one.
+    fn move_list_parameter(&mut self, item_index: usize, param_index: usize, up: bool) {
+        let parameters = match self.current_list {
+            EntityList::Functions => &mut self.functions[item_index].parameters,
+            EntityList::Events => &mut self.events[item_index].parameters,
+        };
+
+        if up && param_index > 0 {
+            parameters.swap(param_index, param_index - 1);
+        } else if !up && param_index + 1 < parameters.len() {
+            parameters.swap(param_index, param_index + 1);
+        }
+    }
+
     fn add_list_item_parameter(&mut self, item_index: usize) {
         let parameter = UiParameter {
-            name: String::from("Parameter name"),
+            name: String::from("parameterName"),
             description: String::from("Parameter description"),
             type_: Some(ParameterType::Any),
         };
@@ -997,11 +3551,11 @@ impl MainLayout {
     fn add_list_item(&mut self) {
         match self.current_list {
             EntityList::Functions => self.functions.push(UiRunnable {
-                name: String::from("Function name"),
+                name: String::from("functionName"),
                 parameters: Vec::new(),
             }),
             EntityList::Events => self.events.push(UiRunnable {
-                name: String::from("Event name"),
+                name: String::from("eventName"),
                 parameters: Vec::new(),
             }),
         }
@@ -1018,16 +3572,188 @@ impl MainLayout {
         }
     }
 
-    fn show_functions(&mut self) {
+    /// Swaps the item with the previous (`up`) or the next one.
+    fn move_list_item(&mut self, index: usize, up: bool) {
+        let items = match self.current_list {
+            EntityList::Functions => &mut self.functions,
+            EntityList::Events => &mut self.events,
+        };
+
+        if up && index > 0 {
+            items.swap(index, index - 1);
+        } else if !up && index + 1 < items.len() {
+            items.swap(index, index + 1);
+        }
+    }
+
+    /// Inserts a copy of the item (with all parameters) right after the item.
+    fn duplicate_list_item(&mut self, index: usize) {
+        let items = match self.current_list {
+            EntityList::Functions => &mut self.functions,
+            EntityList::Events => &mut self.events,
+        };
+
+        let mut item = items[index].clone();
+        item.name = format!("{}_copy", item.name);
+        items.insert(index + 1, item);
+    }
+
+    /// Moves the function to the end of the events list (or the event to functions).
+    fn move_list_item_to_other_list(&mut self, index: usize) {
+        match self.current_list {
+            EntityList::Functions => {
+                let item = self.functions.remove(index);
+                self.events.push(item);
+            }
+            EntityList::Events => {
+                let item = self.events.remove(index);
+                self.functions.push(item);
+            }
+        }
+    }
+
+    fn show_functions(&mut self, app_config: &mut ApplicationConfig) {
         self.current_list = EntityList::Functions;
+        self.is_swf_report_shown = false;
+        self.save_active_tab(TAB_FUNCTIONS, app_config);
     }
 
-    fn show_events(&mut self) {
+    fn show_events(&mut self, app_config: &mut ApplicationConfig) {
         self.current_list = EntityList::Events;
+        self.is_swf_report_shown = false;
+        self.save_active_tab(TAB_EVENTS, app_config);
+    }
+
+    fn show_swf_report(&mut self, app_config: &mut ApplicationConfig) {
+        self.is_swf_report_shown = true;
+        self.save_active_tab(TAB_SWF_REPORT, app_config);
+    }
+
+    /// Changes the width of the XML preview and remembers it for the next start.
+    fn update_xml_preview_split(&mut self, split: u16, app_config: &mut ApplicationConfig) {
+        self.xml_preview_split = split.clamp(XML_PREVIEW_SPLIT_RANGE.0, XML_PREVIEW_SPLIT_RANGE.1);
+
+        app_config.xml_preview_split = self.xml_preview_split;
+        if let Err(app_error) = app_config.save() {
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!(
+                    "Failed to save configuration file to {}.\n\nError: {}",
+                    app_config.get_config_file_path(),
+                    app_error
+                ),
+            );
+        }
+    }
+
+    /// Remembers the opened tab so that it's opened on the next start.
+    fn save_active_tab(&mut self, tab: &str, app_config: &mut ApplicationConfig) {
+        if app_config.active_tab == tab {
+            return;
+        }
+
+        app_config.active_tab = tab.to_string();
+        if let Err(app_error) = app_config.save() {
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!(
+                    "Failed to save configuration file to {}.\n\nError: {}",
+                    app_config.get_config_file_path(),
+                    app_error
+                ),
+            );
+        }
+    }
+
+    fn update_align_mode(&mut self, align_mode: AlignMode) {
+        self.align_mode = Some(align_mode);
+
+        if self.width == 0 && self.height == 0 {
+            self.use_stage_size();
+        }
+    }
+
+    fn update_scale(&mut self, scale: bool) {
+        self.scale = scale;
+    }
+
+    fn update_maximize(&mut self, maximize: bool) {
+        self.maximize = maximize;
+
+        if self.width == 0 && self.height == 0 {
+            self.use_stage_size();
+        }
+    }
+
+    fn update_width(&mut self, width: String) {
+        if let Some(width) = self.parse_size(&width) {
+            self.width = width;
+        }
+    }
+
+    fn update_height(&mut self, height: String) {
+        if let Some(height) = self.parse_size(&height) {
+            self.height = height;
+        }
+    }
+
+    /// Parses width or height entered by the user.
+    ///
+    /// ## Return
+    /// `None` if the value is not a valid size (an error message is shown), otherwise parsed value.
+    fn parse_size(&mut self, size: &str) -> Option<u32> {
+        if size.is_empty() {
+            return Some(0);
+        }
+
+        let result = size.parse::<u32>();
+        if let Err(e) = result {
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!("Failed to convert size to unsigned integer. Error: {}", e,),
+            );
+            return None;
+        }
+
+        Some(result.unwrap())
+    }
+
+    /// Sets width and height of the element to the stage size of the selected SWF movie
+    /// (if the stage size is known and the current alignment mode uses a fixed size).
+    fn use_stage_size(&mut self) {
+        if !self.is_size_used() {
+            return;
+        }
+
+        if let Some(swf_info) = &self.swf_info {
+            self.width = swf_info.stage_width;
+            self.height = swf_info.stage_height;
+        }
     }
 
-    fn update_fullscreen(&mut self, fullscreen: bool) {
-        self.fullscreen = fullscreen;
+    /// Tells whether width and height are used in the current alignment mode.
+    fn is_size_used(&self) -> bool {
+        self.align_mode
+            .unwrap_or_default()
+            .has_fixed_size(self.maximize)
+    }
+
+    /// Compares element size with the stage size of the selected SWF movie.
+    ///
+    /// ## Return
+    /// `None` if sizes are equal or can't be compared, otherwise `Some((width, height))`
+    /// with the stage size of the SWF movie.
+    fn get_stage_size_mismatch(&self) -> Option<(u32, u32)> {
+        if !self.is_size_used() {
+            return None;
+        }
+
+        let swf_info = self.swf_info.as_ref()?;
+        if swf_info.stage_width == self.width && swf_info.stage_height == self.height {
+            return None;
+        }
+
+        Some((swf_info.stage_width, swf_info.stage_height))
     }
 
     fn update_horizontal_align(&mut self, halign: HAlign) {
@@ -1041,15 +3767,13 @@ impl MainLayout {
     fn update_gfx_layer(&mut self, gfx_layer: String) {
         let result = gfx_layer.parse::<usize>();
         if let Err(e) = result {
-            MessageDialog::new()
-                .set_type(MessageType::Error)
-                .set_title("Error")
-                .set_text(&format!(
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!(
                     "Failed to convert GFx layer to unsigned integer. Error: {}",
                     e,
-                ))
-                .show_alert()
-                .unwrap();
+                ),
+            );
             return;
         }
         self.gfx_layer = result.unwrap();
@@ -1063,92 +3787,330 @@ impl MainLayout {
         self.ui_element_name = element_name;
     }
 
-    fn select_xml_output_path(&mut self) {
-        // Get path to output .xml files.
-        let path = FileDialog::new().show_open_single_dir().unwrap();
-        if path.is_none() {
-            return;
+    /// Shows the system dialog to pick a path without blocking the UI
+    /// (the result is sent as `PathPicked`), the built-in path browser is shown
+    /// if there is no system dialog.
+    ///
+    /// ## Arguments
+    /// * `target`: field to pick the path for.
+    /// * `location`: directory (or file in the directory) to start from (may be empty).
+    fn pick_path(&mut self, target: PathTarget, location: String) -> Command<ApplicationMessage> {
+        // Only one dialog at a time.
+        if self.is_path_picker_open || self.path_browser.is_some() {
+            return Command::none();
         }
-        let path = path.unwrap();
 
-        // Save.
-        self.path_to_xml_dir = path.to_string_lossy().to_string();
+        if let Some(error) = Self::get_file_dialog_error() {
+            self.open_path_browser(target, &location, error);
+            return Command::none();
+        }
 
-        // See if an XML file exists.
-        if Path::new(&self.path_to_swf_file).exists() {
-            let swf_file_name = Path::new(&self.path_to_swf_file).file_stem();
-            if swf_file_name.is_none() {
-                return;
+        let mut location = PathBuf::from(location);
+        if location.is_file() {
+            location = location.parent().map(Path::to_path_buf).unwrap_or_default();
+        }
+
+        self.is_path_picker_open = true;
+        Command::perform(
+            async move {
+                let mut dialog = AsyncFileDialog::new();
+                if location.is_dir() {
+                    dialog = dialog.set_directory(&location);
+                }
+
+                let file = match target {
+                    PathTarget::SwfFile => {
+                        dialog.add_filter("SWF Movie", &["swf"]).pick_file().await
+                    }
+                    PathTarget::GfxExportBin => {
+                        #[cfg(windows)]
+                        {
+                            dialog = dialog.add_filter("GFxExport file", &["exe"]);
+                        }
+                        dialog.pick_file().await
+                    }
+                    PathTarget::GfxDir | PathTarget::XmlDir => dialog.pick_folder().await,
+                };
+
+                file.map(|file| file.path().to_string_lossy().to_string())
+            },
+            move |path| {
+                ApplicationMessage::MainLayoutMessage(MainLayoutMessage::PathPicked(target, path))
+            },
+        )
+    }
+
+    /// Tells why the system file dialog can't be shown.
+    ///
+    /// ## Return
+    /// `None` if the dialog can be shown, otherwise the reason.
+    fn get_file_dialog_error() -> Option<String> {
+        // Dialogs on Linux need a display server (for example, in containers there may be none).
+        #[cfg(target_os = "linux")]
+        {
+            let is_variable_set =
+                |name: &str| std::env::var_os(name).map_or(false, |v| !v.is_empty());
+            if !is_variable_set("DISPLAY") && !is_variable_set("WAYLAND_DISPLAY") {
+                return Some(String::from(
+                    "neither DISPLAY nor WAYLAND_DISPLAY environment variable is set",
+                ));
             }
-            let swf_file_name = swf_file_name.unwrap().to_string_lossy().to_string();
+        }
 
-            let result = self.check_if_xml_exists(&self.path_to_xml_dir, &swf_file_name);
-            if result.is_err() {
-                return;
+        None
+    }
+
+    /// Shows the built-in path browser (when the system dialog is not available).
+    ///
+    /// ## Arguments
+    /// * `target`: field to pick the path for.
+    /// * `location`: directory (or file in the directory) to start from (may be empty).
+    /// * `error`: why the system dialog is not available.
+    fn open_path_browser(&mut self, target: PathTarget, location: &str, error: String) {
+        self.notifications.show_toast(
+            NotificationKind::Warning,
+            format!(
+                "The file dialog is not available, using the built-in browser. Error: {}",
+                error
+            ),
+        );
+
+        let path_browser = match target {
+            PathTarget::GfxExportBin => PathBrowser::new(location, false, Vec::new()),
+            PathTarget::SwfFile => PathBrowser::new(location, false, vec!["swf"]),
+            PathTarget::GfxDir | PathTarget::XmlDir => PathBrowser::new(location, true, Vec::new()),
+        };
+        self.path_browser = Some((target, path_browser));
+    }
+
+    /// Uses the path that was picked (or typed) by the user.
+    fn set_path(&mut self, target: PathTarget, path: String, app_config: &mut ApplicationConfig) {
+        match target {
+            PathTarget::GfxExportBin => {
+                self.path_to_gfxexport_bin = path;
+
+                // Save to config.
+                self.store_profile(app_config);
+                if let Err(app_error) = app_config.save() {
+                    self.notifications.show_message(
+                        NotificationKind::Error,
+                        format!(
+                            "Failed to save configuration file to {}.\n\nError: {}",
+                            app_config.get_config_file_path(),
+                            app_error
+                        ),
+                    );
+                }
             }
-            let xml_path = result.unwrap();
+            PathTarget::SwfFile => {
+                self.open_swf_file(Path::new(&path), app_config);
+                self.read_existing_xml(true);
+                self.remember_recent_file(app_config);
+            }
+            PathTarget::GfxDir => self.path_to_gfx_dir = path,
+            PathTarget::XmlDir => {
+                self.path_to_xml_dir = path;
+
+                // See if an XML file exists.
+                self.read_existing_xml(true);
+            }
+        }
+    }
+
+    /// Opens files specified on the command line (the same way as dropped files).
+    pub fn open_files(&mut self, paths: Vec<PathBuf>, app_config: &mut ApplicationConfig) {
+        for path in paths {
+            self.open_dropped_file(path, app_config);
+        }
+    }
+
+    /// Opens a file that was dropped onto the window.
+    ///
+    /// `.swf` files are opened (if multiple files are dropped, the first one is opened
+    /// and others are queued), UIElement `.xml` files are loaded into the fields.
+    fn open_dropped_file(&mut self, path: PathBuf, app_config: &mut ApplicationConfig) {
+        let is_same_drop = self
+            .last_file_drop_time
+            .map(|time| time.elapsed() < FILE_DROP_BATCH_INTERVAL)
+            .unwrap_or(false);
+        self.last_file_drop_time = Some(Instant::now());
 
-            self.get_data_from_existing_xml(&xml_path);
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "swf" => {
+                if !is_same_drop {
+                    self.swf_queue.clear();
+                }
+
+                // Don't open files while the user is being asked something.
+                if is_same_drop || self.notifications.get_modal().is_some() {
+                    self.swf_queue.push(path);
+                } else {
+                    self.set_path(
+                        PathTarget::SwfFile,
+                        path.to_string_lossy().to_string(),
+                        app_config,
+                    );
+                }
+            }
+            "xml" => {
+                // Reading the file would change data the question may refer to.
+                if self.notifications.get_modal().is_some() {
+                    self.notifications.show_toast(
+                        NotificationKind::Warning,
+                        format!(
+                            "Answer the question first, ignoring the dropped file \"{}\".",
+                            path.to_string_lossy()
+                        ),
+                    );
+                    return;
+                }
+
+                self.get_data_from_existing_xml(&path.to_string_lossy(), false);
+            }
+            _ => self.notifications.show_toast(
+                NotificationKind::Warning,
+                format!(
+                    "Only .swf and .xml files can be dropped, ignoring \"{}\".",
+                    path.to_string_lossy()
+                ),
+            ),
         }
     }
 
-    fn select_gfx_output_path(&mut self) {
-        // Get path to output .gfx files.
-        let path = FileDialog::new().show_open_single_dir().unwrap();
-        if path.is_none() {
+    /// Opens the next `.swf` file of the dropped files (if there is one).
+    fn open_next_queued_swf_file(&mut self, app_config: &mut ApplicationConfig) {
+        if self.swf_queue.is_empty() {
             return;
         }
-        let path = path.unwrap();
 
-        // Save.
-        self.path_to_gfx_dir = path.to_string_lossy().to_string();
+        let path = self.swf_queue.remove(0);
+        self.notifications.show_toast(
+            NotificationKind::Info,
+            format!(
+                "Opened the next queued file \"{}\" ({} left in the queue).",
+                path.to_string_lossy(),
+                self.swf_queue.len()
+            ),
+        );
+        self.set_path(
+            PathTarget::SwfFile,
+            path.to_string_lossy().to_string(),
+            app_config,
+        );
+    }
+
+    fn update_path_text(&mut self, target: PathTarget, path: String) {
+        match target {
+            PathTarget::GfxExportBin => self.path_to_gfxexport_bin = path,
+            // Opened when submitted.
+            PathTarget::SwfFile => self.swf_file_input = path,
+            PathTarget::GfxDir => self.path_to_gfx_dir = path,
+            PathTarget::XmlDir => self.path_to_xml_dir = path,
+        }
     }
 
-    fn select_swf_file_path(&mut self, app_config: &mut ApplicationConfig) {
-        // Get path to .swf file.
-        let path = FileDialog::new()
-            .set_location(&app_config.last_used_swf_dir)
-            .add_filter("SWF Movie", &["swf"])
-            .show_open_single_file()
-            .unwrap();
-        if path.is_none() {
+    /// Uses the path that was typed by the user (after Enter is pressed).
+    fn submit_path_text(&mut self, target: PathTarget, app_config: &mut ApplicationConfig) {
+        let path = match target {
+            PathTarget::GfxExportBin => self.path_to_gfxexport_bin.clone(),
+            PathTarget::SwfFile => self.swf_file_input.trim().to_string(),
+            PathTarget::GfxDir => self.path_to_gfx_dir.clone(),
+            PathTarget::XmlDir => self.path_to_xml_dir.clone(),
+        };
+
+        if target == PathTarget::SwfFile && !Path::new(&path).is_file() {
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!("The file \"{}\" does not exist.", path),
+            );
             return;
         }
-        let path = path.unwrap();
 
+        self.set_path(target, path, app_config);
+    }
+
+    /// Fills fields for the specified `.swf` file (without reading the existing `.xml` file).
+    fn open_swf_file(&mut self, path: &Path, app_config: &mut ApplicationConfig) {
         // Save.
         self.path_to_swf_file = path.to_string_lossy().to_string();
+        self.swf_file_input = self.path_to_swf_file.clone();
+
+        // Read stage size of the movie.
+        self.swf_info = None;
+        self.gfx_summary = None;
+        match SwfManager::read_info(&self.path_to_swf_file) {
+            Ok(swf_info) => self.swf_info = Some(swf_info),
+            Err(app_error) => {
+                self.notifications.show_message(
+                    NotificationKind::Warning,
+                    format!(
+                        "Failed to read the header of the .swf file, \
+                        the stage size will not be available. Error: {}",
+                        app_error
+                    ),
+                );
+            }
+        }
+        self.use_stage_size();
 
         // Set UI elemnt names.
         self.ui_elements_name = path.file_stem().unwrap().to_string_lossy().to_string();
         self.ui_element_name = self.ui_elements_name.clone();
 
-        // Save paths to output directies.
-        if path.parent().is_some() && path.parent().unwrap().parent().is_some() {
-            // Set path to .gfx and .xml files.
-            let path_to_gfx = path.parent().unwrap().parent().unwrap();
-            let mut path_to_xml = path_to_gfx.to_path_buf();
-            path_to_xml.push("UIElements");
-
-            self.path_to_gfx_dir = path_to_gfx.to_string_lossy().to_string();
-            self.path_to_xml_dir = path_to_xml.to_string_lossy().to_string();
+        // Look for a project config (its values take precedence over user settings).
+        self.store_profile(app_config);
+        if let Err(app_error) = app_config.load_project_config(path) {
+            self.notifications.show_message(
+                NotificationKind::Warning,
+                format!(
+                    "Failed to read the project config, only user settings will be used. \
+                    Error: {}",
+                    app_error.get_message()
+                ),
+            );
+        }
+        self.path_to_project_config = app_config
+            .project_config
+            .as_ref()
+            .map(|project_config| project_config.path_to_file.to_string_lossy().to_string());
+        self.profile_names = app_config.get_profile_names();
+        self.project_fonts = app_config.get_project_fonts();
 
-            // Save directory to config.
-            app_config.last_used_swf_dir = path.parent().unwrap().to_string_lossy().to_string();
-            if let Err(app_error) = app_config.save() {
-                MessageDialog::new()
-                    .set_type(MessageType::Error)
-                    .set_title("Error")
-                    .set_text(&format!(
-                        "Failed to save configuration file to {}.\n\nError: {}",
-                        ApplicationConfig::get_config_file_path().to_string_lossy(),
-                        app_error
-                    ))
-                    .show_alert()
-                    .unwrap();
+        // Look for a CRYENGINE project to put output files to its UI directories.
+        self.cry_project = None;
+        match CryProjectManager::find(path) {
+            Ok(cry_project) => self.cry_project = cry_project,
+            Err(app_error) => {
+                self.notifications.show_message(
+                    NotificationKind::Warning,
+                    format!(
+                        "Failed to read the CRYENGINE project file, \
+                        output directories will be guessed. Error: {}",
+                        app_error.get_message()
+                    ),
+                );
             }
         }
 
+        // Use the profile that was last used for this directory.
+        let swf_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let profile = app_config.get_profile_for_swf_dir(&swf_dir.to_string_lossy());
+        self.apply_profile(&profile);
+
+        // Set paths to output directories.
+        self.update_output_dirs();
+
+        // Remember directory and profile.
+        app_config.last_used_swf_dir = swf_dir.to_string_lossy().to_string();
+        app_config.set_last_used_profile(&swf_dir.to_string_lossy(), &profile.name);
+    }
+
+    /// Reads the existing `.xml` file of the selected `.swf` file (if found).
+    fn read_existing_xml(&mut self, should_ask: bool) {
         // See if an XML file exists.
         if Path::new(&self.path_to_swf_file).exists() {
             let swf_file_name = Path::new(&self.path_to_swf_file).file_stem();
@@ -1163,53 +4125,288 @@ impl MainLayout {
             }
             let xml_path = result.unwrap();
 
-            self.get_data_from_existing_xml(&xml_path);
+            self.get_data_from_existing_xml(&xml_path, should_ask);
         }
     }
 
-    fn select_gfx_bin_path(&mut self, app_config: &mut ApplicationConfig) {
-        #[cfg(windows)]
-        {
-            // Get path to GFxExport1.exe file.
-            let path = FileDialog::new()
-                .add_filter("GFxExport file", &["exe"])
-                .show_open_single_file()
-                .unwrap();
-            if path.is_none() {
-                return;
+    /// Reopens the element that was opened last (if its `.swf` file still exists).
+    pub fn restore_last_element(&mut self, app_config: &mut ApplicationConfig) {
+        let label = match app_config.recent_files.first() {
+            Some(recent_file) if Path::new(&recent_file.path_to_swf).is_file() => {
+                recent_file.get_label()
             }
-            let path = path.unwrap();
+            _ => return,
+        };
+
+        self.open_recent_file(label, app_config);
+    }
 
-            // Save to UI.
-            self.path_to_gfxexport_bin = path.to_string_lossy().to_string();
+    /// Opens the `.swf` file from the recent files list and restores all fields.
+    fn open_recent_file(&mut self, label: String, app_config: &mut ApplicationConfig) {
+        let recent_file = self
+            .recent_files
+            .iter()
+            .find(|recent_file| recent_file.get_label() == label)
+            .cloned();
+        if recent_file.is_none() {
+            return;
         }
+        let recent_file = recent_file.unwrap();
 
-        #[cfg(not(windows))]
-        {
-            // Get path to GFxExport1 file.
-            let path = FileDialog::new().show_open_single_file().unwrap();
-            if path.is_none() {
+        if !Path::new(&recent_file.path_to_swf).is_file() {
+            self.remove_missing_recent_entries(app_config);
+            self.notifications.show_message(
+                NotificationKind::Warning,
+                format!(
+                    "The file \"{}\" no longer exists, it was removed from the list.",
+                    recent_file.path_to_swf
+                ),
+            );
+            return;
+        }
+
+        self.open_swf_file(Path::new(&recent_file.path_to_swf), app_config);
+
+        // Restore the profile.
+        if let Some(profile) = app_config.get_profile(&recent_file.profile) {
+            self.apply_profile(&profile);
+            if let Some(swf_dir) = Path::new(&recent_file.path_to_swf).parent() {
+                app_config.set_last_used_profile(&swf_dir.to_string_lossy(), &profile.name);
+            }
+        }
+
+        // Restore output directories and names.
+        self.path_to_gfx_dir = recent_file.path_to_gfx_dir;
+        self.path_to_xml_dir = recent_file.path_to_xml_dir;
+        self.output_dirs_error = None;
+        self.ui_elements_name = recent_file.ui_elements_name;
+        self.ui_element_name = recent_file.ui_element_name;
+
+        self.read_existing_xml(false);
+        self.remember_recent_file(app_config);
+    }
+
+    /// Shows a file dialog to select a `.swf` file in the directory of the recent project.
+    fn open_recent_project(
+        &mut self,
+        path_to_project: String,
+        app_config: &mut ApplicationConfig,
+    ) -> Command<ApplicationMessage> {
+        let path = Path::new(&path_to_project);
+        if !path.is_file() {
+            self.remove_missing_recent_entries(app_config);
+            self.notifications.show_message(
+                NotificationKind::Warning,
+                format!(
+                    "The file \"{}\" no longer exists, it was removed from the list.",
+                    path_to_project
+                ),
+            );
+            return Command::none();
+        }
+
+        let project_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        self.pick_path(
+            PathTarget::SwfFile,
+            project_dir.to_string_lossy().to_string(),
+        )
+    }
+
+    /// Saves the profile, project fonts and the element (to recent elements) without generating.
+    fn save_project(&mut self, app_config: &mut ApplicationConfig) {
+        self.store_profile(app_config);
+        app_config.set_project_fonts(&self.project_fonts);
+        if self.path_to_swf_file.is_empty() {
+            // Nothing to remember as a recent element.
+            if let Err(app_error) = app_config.save() {
+                self.notifications.show_message(
+                    NotificationKind::Error,
+                    format!(
+                        "Failed to save configuration file to {}.\n\nError: {}",
+                        app_config.get_config_file_path(),
+                        app_error
+                    ),
+                );
                 return;
             }
-            let path = path.unwrap();
+        } else {
+            if let Some(swf_dir) = Path::new(&self.path_to_swf_file).parent() {
+                app_config.set_last_used_profile(&swf_dir.to_string_lossy(), &self.profile_name);
+            }
+            self.remember_recent_file(app_config);
+        }
+
+        self.notifications.show_toast(
+            NotificationKind::Success,
+            format!("Saved to {}.", app_config.get_config_file_path()),
+        );
+    }
+
+    /// Adds the selected `.swf` file and its project to recent lists and saves the config.
+    fn remember_recent_file(&mut self, app_config: &mut ApplicationConfig) {
+        app_config.add_recent_file(RecentFile {
+            path_to_swf: self.path_to_swf_file.clone(),
+            path_to_gfx_dir: self.path_to_gfx_dir.clone(),
+            path_to_xml_dir: self.path_to_xml_dir.clone(),
+            ui_elements_name: self.ui_elements_name.clone(),
+            ui_element_name: self.ui_element_name.clone(),
+            profile: self.profile_name.clone(),
+        });
 
-            // Save to UI.
-            self.path_to_gfxexport_bin = path.to_string_lossy().to_string();
+        let path_to_project = match &self.cry_project {
+            Some(cry_project) => Some(cry_project.path_to_file.to_string_lossy().to_string()),
+            None => self.path_to_project_config.clone(),
+        };
+        if let Some(path_to_project) = path_to_project {
+            app_config.add_recent_project(&path_to_project);
         }
 
-        // Save to config.
-        app_config.path_to_gfxexport_bin = self.path_to_gfxexport_bin.clone();
+        self.recent_files = app_config.recent_files.clone();
+        self.recent_projects = app_config.recent_projects.clone();
+
         if let Err(app_error) = app_config.save() {
-            MessageDialog::new()
-                .set_type(MessageType::Error)
-                .set_title("Error")
-                .set_text(&format!(
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!(
                     "Failed to save configuration file to {}.\n\nError: {}",
-                    ApplicationConfig::get_config_file_path().to_string_lossy(),
+                    app_config.get_config_file_path(),
                     app_error
-                ))
-                .show_alert()
-                .unwrap();
+                ),
+            );
+        }
+    }
+
+    fn remove_missing_recent_entries(&mut self, app_config: &mut ApplicationConfig) {
+        app_config.remove_missing_recent_entries();
+        self.recent_files = app_config.recent_files.clone();
+        self.recent_projects = app_config.recent_projects.clone();
+
+        if let Err(app_error) = app_config.save() {
+            self.notifications.show_message(
+                NotificationKind::Error,
+                format!(
+                    "Failed to save configuration file to {}.\n\nError: {}",
+                    app_config.get_config_file_path(),
+                    app_error
+                ),
+            );
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates an item with parameters without descriptions.
+    fn make_runnable(name: &str, parameter_names: &[&str]) -> UiRunnable {
+        UiRunnable {
+            name: name.to_string(),
+            parameters: parameter_names
+                .iter()
+                .map(|name| UiParameter {
+                    name: name.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn get_identifier_error_accepts_identifiers() {
+        assert_eq!(get_identifier_error("OnClick"), None);
+        assert_eq!(get_identifier_error("_set_value2"), None);
+    }
+
+    #[test]
+    fn get_identifier_error_rejects_invalid_names() {
+        assert_eq!(
+            get_identifier_error(""),
+            Some(String::from("name is empty"))
+        );
+        assert_eq!(
+            get_identifier_error("2d_view"),
+            Some(String::from("\"2d_view\" should not start with a digit"))
+        );
+        assert_eq!(
+            get_identifier_error("on-click"),
+            Some(String::from(
+                "\"on-click\" contains \"-\", only letters, digits and underscores are allowed"
+            ))
+        );
+        assert!(get_identifier_error("on click").is_some());
+        assert!(get_identifier_error("größe").is_some());
+    }
+
+    #[test]
+    fn get_name_error_detects_duplicates() {
+        let items = vec![
+            make_runnable("Show", &[]),
+            make_runnable("Hide", &[]),
+            make_runnable("Show", &[]),
+        ];
+
+        assert_eq!(
+            UiRunnable::get_name_error(&items, 0),
+            Some(String::from("\"Show\" is used more than once"))
+        );
+        assert_eq!(UiRunnable::get_name_error(&items, 1), None);
+        assert!(UiRunnable::get_name_error(&items, 2).is_some());
+    }
+
+    #[test]
+    fn get_name_error_checks_identifier_first() {
+        let items = vec![make_runnable("", &[]), make_runnable("", &[])];
+
+        assert_eq!(
+            UiRunnable::get_name_error(&items, 0),
+            Some(String::from("name is empty"))
+        );
+    }
+
+    #[test]
+    fn get_parameter_name_error_checks_parameters_of_the_item() {
+        let item = make_runnable("SetText", &["text", "1st", "text", "color"]);
+
+        assert_eq!(
+            item.get_parameter_name_error(0),
+            Some(String::from("parameter \"text\" is used more than once"))
+        );
+        assert_eq!(
+            item.get_parameter_name_error(1),
+            Some(String::from("\"1st\" should not start with a digit"))
+        );
+        assert_eq!(item.get_parameter_name_error(3), None);
+
+        // Same names in different items are allowed.
+        let items = vec![
+            make_runnable("Show", &["id"]),
+            make_runnable("Hide", &["id"]),
+        ];
+        assert_eq!(items[0].get_parameter_name_error(0), None);
+        assert_eq!(UiRunnable::get_name_error(&items, 0), None);
+    }
+
+    #[test]
+    fn is_text_matching_filter_ignores_case() {
+        assert!(is_text_matching_filter("SetVisible", "visible"));
+        assert!(is_text_matching_filter("SetVisible", "VISIBLE"));
+        assert!(is_text_matching_filter("set_visible", "Set_V"));
+        assert!(!is_text_matching_filter("SetVisible", "hidden"));
+        // Empty filter matches any text.
+        assert!(is_text_matching_filter("", ""));
+        assert!(is_text_matching_filter("SetVisible", ""));
+    }
+
+    #[test]
+    fn is_matching_filter_checks_names_and_descriptions() {
+        let mut item = make_runnable("SetText", &["value"]);
+        item.parameters[0].description = String::from("Text to show on the Button");
+
+        assert!(item.is_matching_filter("settext"));
+        assert!(item.is_matching_filter("VALUE"));
+        assert!(item.is_matching_filter("button"));
+        assert!(item.is_matching_filter(""));
+        assert!(!item.is_matching_filter("color"));
+    }
+}
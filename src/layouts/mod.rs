@@ -1 +1,6 @@
+pub mod alignment_preview;
+pub mod command_palette;
 pub mod main_layout;
+pub mod notifications;
+pub mod path_browser;
+pub mod undo_history;
@@ -0,0 +1,149 @@
+// Std.
+use std::time::{Duration, Instant};
+
+/// How long toasts are shown.
+pub const TOAST_DURATION: Duration = Duration::from_secs(8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                NotificationKind::Info => "Info",
+                NotificationKind::Success => "Success",
+                NotificationKind::Warning => "Warning",
+                NotificationKind::Error => "Error",
+            }
+        )
+    }
+}
+
+/// Message that is shown instead of the layout until the user closes it.
+pub struct Modal<Message> {
+    pub kind: NotificationKind,
+    pub text: String,
+    /// Message to send if the user answers "Yes", `None` if this is not a question.
+    pub on_confirm: Option<Message>,
+    /// Message to send if the user answers "No" (or closes the message).
+    pub on_cancel: Option<Message>,
+}
+
+/// Message that is shown above the layout and disappears after `TOAST_DURATION`.
+pub struct Toast {
+    pub kind: NotificationKind,
+    pub text: String,
+    created_time: Instant,
+}
+
+/// Modals and toasts of a layout (used instead of native message boxes).
+pub struct Notifications<Message> {
+    /// Modals in the order they were added, only the first one is shown.
+    modals: Vec<Modal<Message>>,
+    toasts: Vec<Toast>,
+}
+
+impl<Message> Default for Notifications<Message> {
+    fn default() -> Self {
+        Self {
+            modals: Vec::new(),
+            toasts: Vec::new(),
+        }
+    }
+}
+
+impl<Message> Notifications<Message> {
+    /// Shows a message that the user has to close before continuing.
+    pub fn show_message(&mut self, kind: NotificationKind, text: String) {
+        self.modals.push(Modal {
+            kind,
+            text,
+            on_confirm: None,
+            on_cancel: None,
+        });
+    }
+
+    /// Asks the user a "Yes/No" question.
+    ///
+    /// ## Arguments
+    /// * `kind`: type of the question.
+    /// * `text`: question to show.
+    /// * `on_confirm`: message to send if the user answers "Yes".
+    /// * `on_cancel`: message to send if the user answers "No".
+    pub fn ask(
+        &mut self,
+        kind: NotificationKind,
+        text: String,
+        on_confirm: Message,
+        on_cancel: Option<Message>,
+    ) {
+        self.modals.push(Modal {
+            kind,
+            text,
+            on_confirm: Some(on_confirm),
+            on_cancel,
+        });
+    }
+
+    /// Shows a message that does not require any action.
+    pub fn show_toast(&mut self, kind: NotificationKind, text: String) {
+        self.toasts.push(Toast {
+            kind,
+            text,
+            created_time: Instant::now(),
+        });
+    }
+
+    /// Returns the modal to show (if any).
+    pub fn get_modal(&self) -> Option<&Modal<Message>> {
+        self.modals.first()
+    }
+
+    /// Closes the shown modal.
+    ///
+    /// ## Arguments
+    /// * `is_confirmed`: whether the user answered "Yes".
+    ///
+    /// ## Return
+    /// Message to send as the answer (if any).
+    pub fn close_modal(&mut self, is_confirmed: bool) -> Option<Message> {
+        if self.modals.is_empty() {
+            return None;
+        }
+
+        let modal = self.modals.remove(0);
+        if is_confirmed {
+            modal.on_confirm
+        } else {
+            modal.on_cancel
+        }
+    }
+
+    pub fn get_toasts(&self) -> &[Toast] {
+        &self.toasts
+    }
+
+    pub fn get_toast_count(&self) -> usize {
+        self.toasts.len()
+    }
+
+    pub fn close_toast(&mut self, index: usize) {
+        if index < self.toasts.len() {
+            self.toasts.remove(index);
+        }
+    }
+
+    /// Removes toasts that were shown for `TOAST_DURATION`.
+    pub fn remove_expired_toasts(&mut self) {
+        self.toasts
+            .retain(|toast| toast.created_time.elapsed() < TOAST_DURATION);
+    }
+}
@@ -0,0 +1,123 @@
+// Std.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Built-in replacement of native file dialogs (used when they are not available).
+pub struct PathBrowser {
+    current_dir: PathBuf,
+    /// Names of subdirectories of the current directory.
+    dirs: Vec<String>,
+    /// Names of files of the current directory that can be selected.
+    files: Vec<String>,
+    /// Extensions of files to show (without a dot), empty to show all files.
+    extensions: Vec<&'static str>,
+    is_dir_selection: bool,
+    error: Option<String>,
+}
+
+impl PathBrowser {
+    /// Creates a browser.
+    ///
+    /// ## Arguments
+    /// * `location`: directory (or file in the directory) to start from,
+    ///   the working directory is used if empty or not found.
+    /// * `is_dir_selection`: whether a directory (not a file) is selected.
+    /// * `extensions`: extensions of files to show (without a dot), empty to show all files.
+    pub fn new(location: &str, is_dir_selection: bool, extensions: Vec<&'static str>) -> Self {
+        let mut start_dir = PathBuf::from(location);
+        if start_dir.is_file() {
+            start_dir = start_dir
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+        }
+        if location.is_empty() || !start_dir.is_dir() {
+            start_dir = env::current_dir().unwrap_or_default();
+        }
+
+        let mut browser = Self {
+            current_dir: PathBuf::new(),
+            dirs: Vec::new(),
+            files: Vec::new(),
+            extensions,
+            is_dir_selection,
+            error: None,
+        };
+        browser.open_dir(start_dir);
+
+        browser
+    }
+
+    /// Shows contents of the specified directory.
+    pub fn open_dir(&mut self, path: PathBuf) {
+        let entries = fs::read_dir(&path);
+        if let Err(e) = entries {
+            self.error = Some(format!(
+                "failed to read the directory \"{}\", error: {}",
+                path.to_string_lossy(),
+                e
+            ));
+            return;
+        }
+
+        self.error = None;
+        self.dirs.clear();
+        self.files.clear();
+        for entry in entries.unwrap().flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let entry_path = entry.path();
+            if entry_path.is_dir() {
+                self.dirs.push(name);
+            } else if !self.is_dir_selection && self.is_extension_shown(&entry_path) {
+                self.files.push(name);
+            }
+        }
+        self.dirs.sort_by_key(|name| name.to_lowercase());
+        self.files.sort_by_key(|name| name.to_lowercase());
+        self.current_dir = path;
+    }
+
+    /// Shows contents of the parent directory (if there is one).
+    pub fn open_parent_dir(&mut self) {
+        if let Some(parent) = self.current_dir.parent() {
+            self.open_dir(parent.to_path_buf());
+        }
+    }
+
+    pub fn get_current_dir(&self) -> &Path {
+        &self.current_dir
+    }
+
+    pub fn get_dirs(&self) -> &[String] {
+        &self.dirs
+    }
+
+    pub fn get_files(&self) -> &[String] {
+        &self.files
+    }
+
+    pub fn is_dir_selection(&self) -> bool {
+        self.is_dir_selection
+    }
+
+    /// Returns the error of the last attempt to open a directory (if it failed).
+    pub fn get_error(&self) -> Option<&String> {
+        self.error.as_ref()
+    }
+
+    fn is_extension_shown(&self, path: &Path) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+
+        match path.extension() {
+            Some(extension) => self
+                .extensions
+                .iter()
+                .any(|shown| extension.to_string_lossy().eq_ignore_ascii_case(shown)),
+            None => false,
+        }
+    }
+}
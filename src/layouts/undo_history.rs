@@ -0,0 +1,227 @@
+// Std.
+use std::time::{Duration, Instant};
+
+/// Maximum number of steps that can be undone.
+const MAX_UNDO_STEPS: usize = 100;
+/// Changes typed into the same field within this time are undone as a single step.
+const TYPING_GROUP_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Undo/redo history of editing states.
+pub struct UndoHistory<State> {
+    undo_steps: Vec<State>,
+    redo_steps: Vec<State>,
+    /// Field that was typed into last (see `add_step`).
+    last_group: Option<String>,
+    last_step_time: Instant,
+}
+
+impl<State> Default for UndoHistory<State> {
+    fn default() -> Self {
+        Self {
+            undo_steps: Vec::new(),
+            redo_steps: Vec::new(),
+            last_group: None,
+            last_step_time: Instant::now(),
+        }
+    }
+}
+
+impl<State: PartialEq> UndoHistory<State> {
+    pub fn can_undo(&self) -> bool {
+        !self.undo_steps.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_steps.is_empty()
+    }
+
+    /// Adds the state before the change to the history (if something was changed),
+    /// consecutive changes of the same field are grouped into a single step.
+    ///
+    /// ## Arguments
+    /// * `state_before_change`: editing state before the change.
+    /// * `current_state`: editing state after the change.
+    /// * `group`: name of the text field that was changed, `None` if the change
+    ///   is always a separate step.
+    pub fn add_step(
+        &mut self,
+        state_before_change: State,
+        current_state: &State,
+        group: Option<String>,
+    ) {
+        self.add_step_at(state_before_change, current_state, group, Instant::now());
+    }
+
+    /// Returns the state to restore, `None` if there is nothing to undo.
+    ///
+    /// ## Arguments
+    /// * `current_state`: editing state that `redo` should restore.
+    pub fn undo(&mut self, current_state: State) -> Option<State> {
+        self.last_group = None;
+
+        let state = self.undo_steps.pop()?;
+        self.redo_steps.push(current_state);
+        Some(state)
+    }
+
+    /// Returns the state to restore, `None` if there is nothing to redo.
+    ///
+    /// ## Arguments
+    /// * `current_state`: editing state that `undo` should restore.
+    pub fn redo(&mut self, current_state: State) -> Option<State> {
+        self.last_group = None;
+
+        let state = self.redo_steps.pop()?;
+        self.undo_steps.push(current_state);
+        Some(state)
+    }
+
+    /// Removes all steps (when another element is opened).
+    pub fn clear(&mut self) {
+        self.undo_steps.clear();
+        self.redo_steps.clear();
+        self.last_group = None;
+    }
+
+    fn add_step_at(
+        &mut self,
+        state_before_change: State,
+        current_state: &State,
+        group: Option<String>,
+        time: Instant,
+    ) {
+        if *current_state == state_before_change {
+            return;
+        }
+
+        // Continue the step if the user keeps typing into the same field.
+        let is_same_step = group.is_some()
+            && group == self.last_group
+            && time.duration_since(self.last_step_time) < TYPING_GROUP_INTERVAL;
+        if !is_same_step {
+            self.undo_steps.push(state_before_change);
+            if self.undo_steps.len() > MAX_UNDO_STEPS {
+                self.undo_steps.remove(0);
+            }
+        }
+
+        self.redo_steps.clear();
+        self.last_group = group;
+        self.last_step_time = time;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Types the text into the field one character at a time.
+    fn type_text(history: &mut UndoHistory<String>, state: &mut String, text: &str, time: Instant) {
+        for character in text.chars() {
+            let state_before_change = state.clone();
+            state.push(character);
+            history.add_step_at(state_before_change, state, Some(String::from("name")), time);
+        }
+    }
+
+    #[test]
+    fn typing_into_the_same_field_is_a_single_step() {
+        let mut history = UndoHistory::default();
+        let mut state = String::new();
+        let time = Instant::now();
+
+        type_text(&mut history, &mut state, "abc", time);
+
+        assert_eq!(history.undo(state.clone()), Some(String::new()));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn typing_is_split_by_pauses_other_fields_and_undo() {
+        let mut history = UndoHistory::default();
+        let mut state = String::new();
+        let time = Instant::now();
+
+        type_text(&mut history, &mut state, "ab", time);
+        let time = time + TYPING_GROUP_INTERVAL;
+        type_text(&mut history, &mut state, "cd", time);
+        assert_eq!(history.undo_steps, vec!["", "ab"]);
+
+        // Another field.
+        history.add_step_at(
+            state.clone(),
+            &String::from("abcd!"),
+            Some(String::from("width")),
+            time,
+        );
+        state.push('!');
+        type_text(&mut history, &mut state, "e", time);
+        assert_eq!(history.undo_steps, vec!["", "ab", "abcd", "abcd!"]);
+
+        // Undo ends the group.
+        let state = history.undo(state).unwrap();
+        let mut state = history.redo(state).unwrap();
+        type_text(&mut history, &mut state, "f", time);
+        assert_eq!(
+            history.undo_steps,
+            vec!["", "ab", "abcd", "abcd!", "abcd!e"]
+        );
+    }
+
+    #[test]
+    fn changes_without_group_are_separate_steps() {
+        let mut history = UndoHistory::default();
+        let time = Instant::now();
+
+        history.add_step_at(1, &2, None, time);
+        history.add_step_at(2, &3, None, time);
+        // Nothing was changed.
+        history.add_step_at(3, &3, None, time);
+
+        assert_eq!(history.undo_steps, vec![1, 2]);
+    }
+
+    #[test]
+    fn oldest_steps_are_removed() {
+        let mut history = UndoHistory::default();
+        let time = Instant::now();
+
+        for state in 0..MAX_UNDO_STEPS + 5 {
+            history.add_step_at(state, &(state + 1), None, time);
+        }
+
+        assert_eq!(history.undo_steps.len(), MAX_UNDO_STEPS);
+        assert_eq!(history.undo_steps[0], 5);
+        assert_eq!(history.undo_steps.last(), Some(&(MAX_UNDO_STEPS + 4)));
+    }
+
+    #[test]
+    fn undo_and_redo_restore_states() {
+        let mut history = UndoHistory::default();
+        let time = Instant::now();
+        history.add_step_at(1, &2, None, time);
+        history.add_step_at(2, &3, None, time);
+
+        assert_eq!(history.undo(3), Some(2));
+        assert_eq!(history.undo(2), Some(1));
+        assert_eq!(history.undo(1), None);
+        assert_eq!(history.redo(1), Some(2));
+        assert!(history.can_undo());
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn new_change_clears_redo() {
+        let mut history = UndoHistory::default();
+        let time = Instant::now();
+        history.add_step_at(1, &2, None, time);
+        assert_eq!(history.undo(2), Some(1));
+        assert!(history.can_redo());
+
+        history.add_step_at(1, &5, None, time);
+
+        assert!(!history.can_redo());
+        assert_eq!(history.redo(5), None);
+        assert_eq!(history.undo(5), Some(1));
+    }
+}
@@ -2,18 +2,36 @@
 // On Windows platform, don't show a console when opening the app.
 #![windows_subsystem = "windows"]
 
+// Std.
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// External.
+use iced::keyboard;
 use iced::window::Position;
-use iced::{executor, window, Application, Command, Element, Renderer, Settings, Size};
+use iced::{
+    executor, subscription, time, window, Application, Command, Element, Event, Renderer, Settings,
+    Subscription,
+};
 
 // Custom.
+use cli::cli_app::CliApp;
 use layouts::main_layout::*;
 use misc::config::ApplicationConfig;
+use misc::overrides::SettingOverrides;
 use misc::theme::Theme;
 
+mod cli;
 mod layouts;
 mod managers;
 mod misc;
 
+/// Windows moves minimized windows to (-32000, -32000).
+const MINIMIZED_WINDOW_POSITION: i32 = -32000;
+/// The window geometry is saved when it was not changed for this long
+/// (to not write the config file while the window is being dragged).
+const WINDOW_GEOMETRY_SAVE_DELAY: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Layout {
     Main,
@@ -22,6 +40,16 @@ pub enum Layout {
 #[derive(Debug, Clone)]
 pub enum ApplicationMessage {
     MainLayoutMessage(MainLayoutMessage),
+    WindowResized(u32, u32),
+    WindowMoved(i32, i32),
+    WindowGeometrySaveTimerTicked,
+}
+
+/// Data passed to the GUI on start.
+pub struct StartupOptions {
+    app_config: ApplicationConfig,
+    /// Files specified on the command line (opened like dropped files).
+    paths_to_open: Vec<PathBuf>,
 }
 
 pub struct ApplicationState {
@@ -30,21 +58,32 @@ pub struct ApplicationState {
     main_layout: MainLayout,
 
     app_config: ApplicationConfig,
+
+    /// When the window geometry was changed (`None` if it's saved).
+    window_geometry_change_time: Option<Instant>,
 }
 
 impl Application for ApplicationState {
     type Message = ApplicationMessage;
     type Theme = Theme;
     type Executor = executor::Default;
-    type Flags = ();
+    type Flags = StartupOptions;
+
+    fn new(options: StartupOptions) -> (Self, Command<ApplicationMessage>) {
+        let mut config = options.app_config;
+
+        let mut main_layout = MainLayout::new(&config);
+        if !main_layout.restore_session(&mut config) && options.paths_to_open.is_empty() {
+            main_layout.restore_last_element(&mut config);
+        }
+        main_layout.open_files(options.paths_to_open, &mut config);
 
-    fn new(_flags: ()) -> (Self, Command<ApplicationMessage>) {
-        let config = ApplicationConfig::new();
         (
             Self {
                 current_layout: Layout::Main,
-                main_layout: MainLayout::new(&config),
+                main_layout,
                 app_config: config,
+                window_geometry_change_time: None,
             },
             Command::none(),
         )
@@ -72,26 +111,114 @@ impl Application for ApplicationState {
             ApplicationMessage::MainLayoutMessage(message) => {
                 self.main_layout.update(message, &mut self.app_config)
             }
+            ApplicationMessage::WindowResized(width, height) => {
+                // Minimized windows have zero size.
+                if width != 0 && height != 0 {
+                    self.app_config.window_size = (width, height);
+                    self.window_geometry_change_time = Some(Instant::now());
+                }
+                Command::none()
+            }
+            ApplicationMessage::WindowMoved(x, y) => {
+                // Minimized windows are moved far outside of the screen on Windows.
+                if x > MINIMIZED_WINDOW_POSITION && y > MINIMIZED_WINDOW_POSITION {
+                    self.app_config.window_position = Some((x, y));
+                    self.window_geometry_change_time = Some(Instant::now());
+                }
+                Command::none()
+            }
+            ApplicationMessage::WindowGeometrySaveTimerTicked => {
+                if let Some(change_time) = self.window_geometry_change_time {
+                    if change_time.elapsed() >= WINDOW_GEOMETRY_SAVE_DELAY {
+                        self.save_window_geometry();
+                        self.window_geometry_change_time = None;
+                    }
+                }
+                Command::none()
+            }
+        }
+    }
+
+    fn subscription(&self) -> Subscription<ApplicationMessage> {
+        let mut subscriptions = vec![
+            Self::get_event_subscription(),
+            self.main_layout
+                .subscription()
+                .map(ApplicationMessage::MainLayoutMessage),
+        ];
+
+        if self.window_geometry_change_time.is_some() {
+            subscriptions.push(
+                time::every(WINDOW_GEOMETRY_SAVE_DELAY)
+                    .map(|_| ApplicationMessage::WindowGeometrySaveTimerTicked),
+            );
+        }
+
+        Subscription::batch(subscriptions)
+    }
+}
+
+impl ApplicationState {
+    fn get_event_subscription() -> Subscription<ApplicationMessage> {
+        subscription::events_with(|event, _status| match event {
+            Event::Window(window::Event::Resized { width, height }) => {
+                Some(ApplicationMessage::WindowResized(width, height))
+            }
+            Event::Window(window::Event::Moved { x, y }) => {
+                Some(ApplicationMessage::WindowMoved(x, y))
+            }
+            Event::Window(window::Event::FileDropped(path)) => Some(
+                ApplicationMessage::MainLayoutMessage(MainLayoutMessage::FileDropped(path)),
+            ),
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }) => MainLayout::get_shortcut_message(key_code, modifiers)
+                .map(ApplicationMessage::MainLayoutMessage),
+            _ => None,
+        })
+    }
+
+    /// Saves the window geometry (the app is closed without notifying us
+    /// so the geometry is saved shortly after it's changed).
+    fn save_window_geometry(&self) {
+        if let Err(app_error) = self.app_config.save() {
+            // Non-critical error.
+            println!("WARNING: {}", app_error.get_message());
         }
     }
 }
 
 fn main() -> iced::Result {
-    let window_size = Size {
-        width: 900,
-        height: 700,
-    };
+    // Run without GUI if some command is specified.
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    if CliApp::is_command(&args) {
+        std::process::exit(CliApp::run(&args));
+    }
+
+    // Setting flags are applied to the GUI too, other arguments are files to open.
+    let (cli_overrides, paths_to_open) = SettingOverrides::from_args(&args).unwrap_or_default();
+
+    let mut app_config = ApplicationConfig::new();
+    app_config.cli_overrides = cli_overrides;
 
-    // Prepare window settings.
+    // Prepare window settings (restore the geometry of the last session).
     let window_settings = window::Settings {
-        size: (window_size.width, window_size.height),
-        position: Position::Centered,
+        size: app_config.window_size,
+        position: match app_config.window_position {
+            Some((x, y)) => Position::Specific(x, y),
+            None => Position::Centered,
+        },
         ..window::Settings::default()
     };
 
     ApplicationState::run(Settings {
         antialiasing: true,
         window: window_settings,
+        flags: StartupOptions {
+            app_config,
+            paths_to_open: paths_to_open.iter().map(PathBuf::from).collect(),
+        },
         ..Settings::default()
     })
 }
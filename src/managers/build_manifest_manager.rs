@@ -0,0 +1,378 @@
+// Std.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// External.
+use configparser::ini::Ini;
+use sha2::{Digest, Sha256};
+
+// Custom.
+use crate::misc::error::AppError;
+
+/// Name of the manifest file that is stored in the output directory.
+const BUILD_MANIFEST_FILE_NAME: &str = "cryengine-ui-helper.manifest.ini";
+const MANIFEST_SWF_HASH: &str = "swf_hash";
+const MANIFEST_EXPORT_OPTIONS: &str = "export_options";
+const MANIFEST_EXPORTER_HASH: &str = "exporter_hash";
+/// Prefix of keys that store hashes of produced files.
+const MANIFEST_OUTPUT_FILE_PREFIX: &str = "output.";
+
+/// Everything that affects the result of the export.
+#[derive(Debug)]
+pub struct ExportInputs {
+    pub swf_hash: String,
+    pub export_options: String,
+    /// `None` if the GFxExport binary could not be read (the export is never skipped then).
+    pub exporter_hash: Option<String>,
+}
+
+/// Keeps track of exported movies so that unchanged movies are not exported again.
+///
+/// The manifest is an `.ini` file in the output directory with a section per movie.
+pub struct BuildManifestManager;
+
+impl BuildManifestManager {
+    /// Collects hashes and options that affect the result of the export.
+    ///
+    /// ## Arguments
+    /// * `path_to_swf`: path to the `.swf` file to export.
+    /// * `path_to_gfxexport_bin`: path to GFxExport binary (or its name to find it in `PATH`).
+    /// * `export_options`: runner and all arguments that will be passed to GFxExport.
+    ///
+    /// ## Return
+    /// `Err(AppError)` if the `.swf` file can't be read, otherwise inputs and
+    /// a warning if the export will never be skipped.
+    pub fn get_inputs(
+        path_to_swf: &str,
+        path_to_gfxexport_bin: &str,
+        export_options: &str,
+    ) -> Result<(ExportInputs, Option<String>), AppError> {
+        // The binary may be unreadable for us (for example, when it's started
+        // through a runner), this should not prevent the export.
+        let exporter_hash =
+            Self::hash_file(&Self::resolve_binary_path(path_to_gfxexport_bin).to_string_lossy());
+        let warning = exporter_hash.as_ref().err().map(|app_error| {
+            format!(
+                "the export will not be skipped because {}",
+                app_error.get_message()
+            )
+        });
+
+        Ok((
+            ExportInputs {
+                swf_hash: Self::hash_file(path_to_swf)?,
+                export_options: export_options.to_string(),
+                exporter_hash: exporter_hash.ok(),
+            },
+            warning,
+        ))
+    }
+
+    /// Checks if the movie was already exported with the same inputs
+    /// and the produced files were not changed since then.
+    ///
+    /// ## Arguments
+    /// * `path_to_output_dir`: directory where GFxExport puts exported files.
+    /// * `swf_file_name`: name of the `.swf` file without extension.
+    /// * `inputs`: current inputs of the export.
+    pub fn is_up_to_date(
+        path_to_output_dir: &str,
+        swf_file_name: &str,
+        inputs: &ExportInputs,
+    ) -> bool {
+        if inputs.exporter_hash.is_none() {
+            return false;
+        }
+
+        let mut manifest = Ini::new_cs();
+        if manifest
+            .load(Path::new(path_to_output_dir).join(BUILD_MANIFEST_FILE_NAME))
+            .is_err()
+        {
+            return false;
+        }
+
+        let section = manifest.get_map_ref().get(swf_file_name);
+        if section.is_none() {
+            return false;
+        }
+        let section = section.unwrap();
+
+        let get_value = |key: &str| section.get(key).cloned().flatten().unwrap_or_default();
+        if get_value(MANIFEST_SWF_HASH) != inputs.swf_hash
+            || get_value(MANIFEST_EXPORT_OPTIONS) != inputs.export_options
+            || Some(get_value(MANIFEST_EXPORTER_HASH)) != inputs.exporter_hash
+        {
+            return false;
+        }
+
+        // Check that produced files were not removed or modified.
+        let mut output_file_count = 0;
+        for (key, hash) in section.iter() {
+            if let Some(file_name) = key.strip_prefix(MANIFEST_OUTPUT_FILE_PREFIX) {
+                let path_to_file = Path::new(path_to_output_dir).join(file_name);
+                match Self::hash_file(&path_to_file.to_string_lossy()) {
+                    Ok(actual_hash) if Some(&actual_hash) == hash.as_ref() => {
+                        output_file_count += 1
+                    }
+                    _ => return false,
+                }
+            }
+        }
+
+        output_file_count != 0
+    }
+
+    /// Records inputs of the finished export and hashes of the produced files.
+    ///
+    /// ## Arguments
+    /// * `path_to_output_dir`: directory where GFxExport puts exported files.
+    /// * `swf_file_name`: name of the `.swf` file without extension.
+    /// * `inputs`: inputs of the export.
+    /// * `output_files`: names of files (in the output directory) produced by the export.
+    pub fn save(
+        path_to_output_dir: &str,
+        swf_file_name: &str,
+        inputs: &ExportInputs,
+        output_files: &[String],
+    ) -> Result<(), AppError> {
+        let path_to_manifest = Path::new(path_to_output_dir).join(BUILD_MANIFEST_FILE_NAME);
+
+        // Keep records of other movies.
+        let mut manifest = Ini::new_cs();
+        if path_to_manifest.exists() {
+            if let Err(e) = manifest.load(&path_to_manifest) {
+                return Err(AppError::new(&e));
+            }
+        }
+        manifest.remove_section(swf_file_name);
+
+        manifest.setstr(swf_file_name, MANIFEST_SWF_HASH, Some(&inputs.swf_hash));
+        manifest.setstr(
+            swf_file_name,
+            MANIFEST_EXPORT_OPTIONS,
+            Some(&inputs.export_options),
+        );
+        manifest.setstr(
+            swf_file_name,
+            MANIFEST_EXPORTER_HASH,
+            inputs.exporter_hash.as_deref(),
+        );
+
+        for file_name in output_files.iter() {
+            let path_to_file = Path::new(path_to_output_dir).join(file_name);
+            let hash = Self::hash_file(&path_to_file.to_string_lossy())?;
+            manifest.setstr(
+                swf_file_name,
+                &format!("{}{}", MANIFEST_OUTPUT_FILE_PREFIX, file_name),
+                Some(&hash),
+            );
+        }
+
+        if let Err(e) = manifest.write(&path_to_manifest) {
+            return Err(AppError::new(&e.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Finds the binary in directories of the `PATH` environment variable
+    /// if only its name is specified and there is no such file in the working directory.
+    ///
+    /// ## Return
+    /// Path to the found binary or the specified path if the binary was not found.
+    fn resolve_binary_path(path_to_bin: &str) -> PathBuf {
+        let path = Path::new(path_to_bin);
+        if path.is_file() || path.components().count() != 1 {
+            return path.to_path_buf();
+        }
+
+        let dirs = env::var_os("PATH").unwrap_or_default();
+        for dir in env::split_paths(&dirs) {
+            let candidate = dir.join(path);
+            if candidate.is_file() {
+                return candidate;
+            }
+
+            #[cfg(windows)]
+            {
+                let candidate = candidate.with_extension("exe");
+                if candidate.is_file() {
+                    return candidate;
+                }
+            }
+        }
+
+        path.to_path_buf()
+    }
+
+    /// Returns SHA-256 hash of the file as a hex string.
+    fn hash_file(path_to_file: &str) -> Result<String, AppError> {
+        let data = fs::read(path_to_file);
+        if let Err(e) = data {
+            return Err(AppError::new(&format!(
+                "failed to read the file \"{}\", error: {}",
+                path_to_file, e
+            )));
+        }
+
+        Ok(Sha256::digest(data.unwrap())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::misc::test_utils::TempDir;
+
+    fn make_inputs() -> ExportInputs {
+        ExportInputs {
+            swf_hash: String::from("swf"),
+            export_options: String::from("-i DDS"),
+            exporter_hash: Some(String::from("exporter")),
+        }
+    }
+
+    /// Writes the `.gfx` file and saves the manifest for it.
+    fn export(dir: &TempDir, inputs: &ExportInputs) {
+        dir.write_file("menu.gfx", b"gfx");
+        BuildManifestManager::save(
+            &dir.get_path().to_string_lossy(),
+            "menu",
+            inputs,
+            &[String::from("menu.gfx")],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn is_up_to_date_with_same_inputs() {
+        let dir = TempDir::new("manifest-same-inputs");
+        export(&dir, &make_inputs());
+
+        assert!(BuildManifestManager::is_up_to_date(
+            &dir.get_path().to_string_lossy(),
+            "menu",
+            &make_inputs()
+        ));
+    }
+
+    #[test]
+    fn is_not_up_to_date_with_changed_inputs() {
+        let dir = TempDir::new("manifest-changed-inputs");
+        export(&dir, &make_inputs());
+        let path_to_dir = dir.get_path().to_string_lossy();
+
+        let mut inputs = make_inputs();
+        inputs.swf_hash = String::from("other");
+        assert!(!BuildManifestManager::is_up_to_date(
+            &path_to_dir,
+            "menu",
+            &inputs
+        ));
+
+        let mut inputs = make_inputs();
+        inputs.export_options = String::from("-i TGA");
+        assert!(!BuildManifestManager::is_up_to_date(
+            &path_to_dir,
+            "menu",
+            &inputs
+        ));
+
+        let mut inputs = make_inputs();
+        inputs.exporter_hash = Some(String::from("other"));
+        assert!(!BuildManifestManager::is_up_to_date(
+            &path_to_dir,
+            "menu",
+            &inputs
+        ));
+
+        // Unknown exporter version.
+        let mut inputs = make_inputs();
+        inputs.exporter_hash = None;
+        assert!(!BuildManifestManager::is_up_to_date(
+            &path_to_dir,
+            "menu",
+            &inputs
+        ));
+
+        // Other movie.
+        assert!(!BuildManifestManager::is_up_to_date(
+            &path_to_dir,
+            "hud",
+            &make_inputs()
+        ));
+    }
+
+    #[test]
+    fn is_not_up_to_date_with_changed_output_files() {
+        let dir = TempDir::new("manifest-changed-outputs");
+        let path_to_dir = dir.get_path().to_string_lossy().to_string();
+
+        export(&dir, &make_inputs());
+        dir.write_file("menu.gfx", b"modified");
+        assert!(!BuildManifestManager::is_up_to_date(
+            &path_to_dir,
+            "menu",
+            &make_inputs()
+        ));
+
+        export(&dir, &make_inputs());
+        fs::remove_file(dir.get_path().join("menu.gfx")).unwrap();
+        assert!(!BuildManifestManager::is_up_to_date(
+            &path_to_dir,
+            "menu",
+            &make_inputs()
+        ));
+    }
+
+    #[test]
+    fn is_not_up_to_date_without_manifest() {
+        let dir = TempDir::new("manifest-missing");
+
+        assert!(!BuildManifestManager::is_up_to_date(
+            &dir.get_path().to_string_lossy(),
+            "menu",
+            &make_inputs()
+        ));
+    }
+
+    #[test]
+    fn get_inputs_warns_when_exporter_can_not_be_read() {
+        let dir = TempDir::new("manifest-inputs");
+        let path_to_swf = dir.write_file("menu.swf", b"swf");
+        let path_to_bin = dir.write_file("gfxexport.exe", b"exe");
+        let path_to_swf = path_to_swf.to_string_lossy();
+
+        let (inputs, warning) =
+            BuildManifestManager::get_inputs(&path_to_swf, &path_to_bin.to_string_lossy(), "")
+                .unwrap();
+        assert!(inputs.exporter_hash.is_some());
+        assert!(warning.is_none());
+
+        let path_to_missing_bin = dir.get_path().join("missing.exe");
+        let (inputs, warning) = BuildManifestManager::get_inputs(
+            &path_to_swf,
+            &path_to_missing_bin.to_string_lossy(),
+            "",
+        )
+        .unwrap();
+        assert!(inputs.exporter_hash.is_none());
+        assert!(warning
+            .unwrap()
+            .starts_with("the export will not be skipped"));
+
+        // Missing movie.
+        assert!(BuildManifestManager::get_inputs(
+            &dir.get_path().join("missing.swf").to_string_lossy(),
+            &path_to_bin.to_string_lossy(),
+            ""
+        )
+        .is_err());
+    }
+}
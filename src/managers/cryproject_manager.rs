@@ -0,0 +1,182 @@
+// Std.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// External.
+use serde_json::Value;
+
+// Custom.
+use crate::misc::error::AppError;
+
+const CRYPROJECT_FILE_EXTENSION: &str = "cryproject";
+/// Assets directory that is used when the project file does not specify one.
+const DEFAULT_ASSETS_DIR_NAME: &str = "Assets";
+/// Directory (relative to the assets directory) that contains `.gfx` files.
+const UI_DIR: [&str; 2] = ["Libs", "UI"];
+/// Directory (relative to the UI directory) that contains `.xml` files.
+const UI_ELEMENTS_DIR_NAME: &str = "UIElements";
+
+/// CRYENGINE project described by a `.cryproject` file.
+#[derive(Debug, Clone)]
+pub struct CryProject {
+    pub path_to_file: PathBuf,
+    /// Directory that contains the `.cryproject` file.
+    pub root_dir: PathBuf,
+    pub assets_dir: PathBuf,
+}
+
+impl CryProject {
+    /// Returns the directory for `.gfx` files (`<assets>/Libs/UI`).
+    pub fn get_gfx_dir(&self) -> PathBuf {
+        let mut path = self.assets_dir.clone();
+        for dir in UI_DIR {
+            path.push(dir);
+        }
+        path
+    }
+
+    /// Returns the directory for `.xml` files (`<assets>/Libs/UI/UIElements`).
+    pub fn get_xml_dir(&self) -> PathBuf {
+        self.get_gfx_dir().join(UI_ELEMENTS_DIR_NAME)
+    }
+}
+
+/// Finds and reads `.cryproject` files.
+pub struct CryProjectManager;
+
+impl CryProjectManager {
+    /// Looks for a `.cryproject` file in the directory of the `.swf` file and its parent directories.
+    ///
+    /// ## Return
+    /// `Ok(None)` if no project file was found, `Err(AppError)` if the found file
+    /// can't be read, otherwise the nearest project.
+    pub fn find(path_to_swf: &Path) -> Result<Option<CryProject>, AppError> {
+        for dir in path_to_swf.ancestors().skip(1) {
+            let entries = fs::read_dir(dir);
+            if entries.is_err() {
+                continue;
+            }
+
+            let path_to_file = entries
+                .unwrap()
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .find(|path| {
+                    path.is_file()
+                        && path
+                            .extension()
+                            .map(|extension| extension == CRYPROJECT_FILE_EXTENSION)
+                            .unwrap_or(false)
+                });
+            if let Some(path_to_file) = path_to_file {
+                return Ok(Some(Self::read(&path_to_file)?));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn read(path_to_file: &Path) -> Result<CryProject, AppError> {
+        let content = fs::read_to_string(path_to_file);
+        if let Err(e) = content {
+            return Err(AppError::new(&format!(
+                "failed to read the project file \"{}\", error: {}",
+                path_to_file.to_string_lossy(),
+                e
+            )));
+        }
+
+        let json = serde_json::from_str::<Value>(&content.unwrap());
+        if let Err(e) = json {
+            return Err(AppError::new(&format!(
+                "failed to parse the project file \"{}\", error: {}",
+                path_to_file.to_string_lossy(),
+                e
+            )));
+        }
+        let json = json.unwrap();
+
+        let root_dir = path_to_file
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .to_path_buf();
+
+        // Assets directories are listed in "content": {"assets": [...]}, the first one is used.
+        let assets_dir_name = json
+            .pointer("/content/assets/0")
+            .and_then(Value::as_str)
+            .unwrap_or(DEFAULT_ASSETS_DIR_NAME);
+
+        Ok(CryProject {
+            path_to_file: path_to_file.to_path_buf(),
+            assets_dir: root_dir.join(assets_dir_name),
+            root_dir,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::misc::test_utils::TempDir;
+
+    /// Creates `Assets/UI` directory and returns path to a `.swf` file in it.
+    fn make_swf_path(dir: &TempDir) -> PathBuf {
+        let path_to_swf_dir = dir.get_path().join("Assets").join("UI");
+        fs::create_dir_all(&path_to_swf_dir).unwrap();
+        path_to_swf_dir.join("menu.swf")
+    }
+
+    #[test]
+    fn find_walks_up_from_swf_file() {
+        let dir = TempDir::new("cryproject-find");
+        let path_to_swf = make_swf_path(&dir);
+        dir.write_file("Game.txt", b"");
+
+        assert!(CryProjectManager::find(&path_to_swf).unwrap().is_none());
+
+        let path_to_file = dir.write_file(
+            "Game.cryproject",
+            br#"{"content": {"assets": ["GameAssets", "Other"]}}"#,
+        );
+        let cry_project = CryProjectManager::find(&path_to_swf).unwrap().unwrap();
+
+        assert_eq!(cry_project.path_to_file, path_to_file);
+        assert_eq!(cry_project.root_dir, dir.get_path());
+        assert_eq!(cry_project.assets_dir, dir.get_path().join("GameAssets"));
+        assert_eq!(
+            cry_project.get_gfx_dir(),
+            dir.get_path().join("GameAssets").join("Libs").join("UI")
+        );
+        assert_eq!(
+            cry_project.get_xml_dir(),
+            cry_project.get_gfx_dir().join("UIElements")
+        );
+    }
+
+    #[test]
+    fn read_uses_default_assets_dir() {
+        let dir = TempDir::new("cryproject-default-assets");
+
+        for content in [
+            r#"{"version": 4}"#,
+            r#"{"content": {"assets": []}}"#,
+            r#"{"content": {"assets": [5]}}"#,
+        ] {
+            let path_to_file = dir.write_file("Game.cryproject", content.as_bytes());
+            let cry_project = CryProjectManager::read(&path_to_file).unwrap();
+
+            assert_eq!(cry_project.assets_dir, dir.get_path().join("Assets"));
+        }
+    }
+
+    #[test]
+    fn find_fails_on_invalid_json() {
+        let dir = TempDir::new("cryproject-invalid");
+        let path_to_swf = make_swf_path(&dir);
+        dir.write_file("Game.cryproject", b"{\"content\": ");
+
+        assert!(CryProjectManager::find(&path_to_swf).is_err());
+    }
+}
@@ -0,0 +1,458 @@
+// Std.
+use std::collections::BTreeMap;
+use std::fs;
+
+// Custom.
+use super::swf_manager::{SwfBitReader, SwfManager, SWF_UNCOMPRESSED_HEADER_SIZE};
+use crate::misc::error::AppError;
+
+// Tags that contain references to other files.
+const TAG_END: u16 = 0;
+const TAG_IMPORT_ASSETS: u16 = 57;
+const TAG_IMPORT_ASSETS_2: u16 = 71;
+const TAG_DEFINE_EXTERNAL_IMAGE: u16 = 1001;
+const TAG_DEFINE_EXTERNAL_IMAGE_2: u16 = 1009;
+
+/// Summary of a `.gfx` file produced by GFxExport.
+pub struct GfxSummary {
+    pub version: u8,
+    pub is_compressed: bool,
+    /// Size of the `.gfx` file in bytes.
+    pub file_size: u64,
+    /// Size of the source `.swf` file in bytes (if known).
+    pub source_file_size: Option<u64>,
+    /// Number of tags by tag name.
+    pub tag_counts: BTreeMap<String, usize>,
+    /// Names of image files referenced by the movie.
+    pub external_images: Vec<String>,
+    /// Names of files imported by the movie.
+    pub imported_files: Vec<String>,
+    /// Problems found in the file (for example, the file is truncated).
+    pub problems: Vec<String>,
+}
+
+impl GfxSummary {
+    /// Returns a short description of the file size compared with the source `.swf` file.
+    pub fn get_size_description(&self) -> String {
+        let mut description = format!("{} KB", (self.file_size + 1023) / 1024);
+
+        let percent = self
+            .source_file_size
+            .and_then(|source_file_size| (self.file_size * 100).checked_div(source_file_size));
+        if let Some(percent) = percent {
+            description += &format!(", {}% of the .swf file", percent);
+        }
+
+        description
+    }
+
+    /// Returns a human-readable report about the file contents
+    /// (problems found in the file are not included).
+    pub fn get_report(&self) -> Vec<String> {
+        let mut report = Vec::new();
+
+        report.push(format!(
+            "version {}, {}, size: {}",
+            self.version,
+            if self.is_compressed {
+                "compressed"
+            } else {
+                "not compressed"
+            },
+            self.get_size_description()
+        ));
+
+        report.push(String::from("tags:"));
+        for (tag_name, count) in self.tag_counts.iter() {
+            report.push(format!("    {}: {}", tag_name, count));
+        }
+
+        report.push(String::from("external images:"));
+        if self.external_images.is_empty() {
+            report.push(String::from("    none"));
+        }
+        for image in self.external_images.iter() {
+            report.push(format!("    {}", image));
+        }
+
+        report.push(String::from("imported files:"));
+        if self.imported_files.is_empty() {
+            report.push(String::from("    none"));
+        }
+        for file in self.imported_files.iter() {
+            report.push(format!("    {}", file));
+        }
+
+        report
+    }
+}
+
+pub struct GfxManager;
+
+impl GfxManager {
+    /// Reads the specified `.gfx` file.
+    ///
+    /// ## Arguments
+    /// * `path_to_gfx`: path to the `.gfx` file to read.
+    /// * `path_to_source_swf`: path to the `.swf` file the `.gfx` file was produced from
+    ///   (used to compare file sizes).
+    ///
+    /// ## Return
+    /// `Err(AppError)` if the file does not exist, is empty or is not a GFX file,
+    /// otherwise `Ok(GfxSummary)` (the summary may contain problems found in the file).
+    pub fn read_summary(
+        path_to_gfx: &str,
+        path_to_source_swf: Option<&str>,
+    ) -> Result<GfxSummary, AppError> {
+        let metadata = fs::metadata(path_to_gfx);
+        if let Err(e) = metadata {
+            return Err(AppError::new(&format!(
+                "failed to read the file \"{}\", error: {}",
+                path_to_gfx, e
+            )));
+        }
+        let file_size = metadata.unwrap().len();
+
+        if file_size == 0 {
+            return Err(AppError::new(&format!(
+                "the file \"{}\" is empty",
+                path_to_gfx
+            )));
+        }
+
+        let data = SwfManager::read_movie_data(path_to_gfx)?;
+        if &data[..3] != b"GFX" && &data[..3] != b"CFX" {
+            return Err(AppError::new(&format!(
+                "the file \"{}\" is not a GFX file",
+                path_to_gfx
+            )));
+        }
+
+        let mut summary = GfxSummary {
+            version: data[3],
+            is_compressed: &data[..3] == b"CFX",
+            file_size,
+            source_file_size: path_to_source_swf
+                .and_then(|path| fs::metadata(path).ok())
+                .map(|metadata| metadata.len()),
+            tag_counts: BTreeMap::new(),
+            external_images: Vec::new(),
+            imported_files: Vec::new(),
+            problems: Vec::new(),
+        };
+
+        // Compare the real size with the size from the header.
+        let expected_size = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
+        if data.len() < expected_size {
+            summary.problems.push(format!(
+                "the file is truncated (expected {} bytes of movie data but found {})",
+                expected_size,
+                data.len()
+            ));
+        }
+
+        Self::read_tags(&data, &mut summary);
+
+        Ok(summary)
+    }
+
+    /// Reads all top-level tags and fills the summary.
+    fn read_tags(data: &[u8], summary: &mut GfxSummary) {
+        let mut reader = SwfBitReader::new(data);
+
+        // Skip header, stage rectangle, frame rate and frame count.
+        let header = reader
+            .skip_bytes(SWF_UNCOMPRESSED_HEADER_SIZE)
+            .and_then(|_| reader.read_rect())
+            .and_then(|_| reader.skip_bytes(4));
+        if header.is_err() {
+            summary
+                .problems
+                .push(String::from("the file is truncated (incomplete header)"));
+            return;
+        }
+
+        let mut is_end_found = false;
+        while !reader.is_at_end() {
+            let tag = reader.read_tag();
+            if tag.is_err() {
+                break;
+            }
+            let (code, body) = tag.unwrap();
+
+            *summary
+                .tag_counts
+                .entry(Self::get_tag_name(code))
+                .or_insert(0) += 1;
+
+            if code == TAG_END {
+                is_end_found = true;
+                break;
+            }
+
+            let mut tag = SwfBitReader::new(body);
+            match code {
+                TAG_IMPORT_ASSETS | TAG_IMPORT_ASSETS_2 => {
+                    if let Ok(url) = tag.read_string() {
+                        if !summary.imported_files.contains(&url) {
+                            summary.imported_files.push(url);
+                        }
+                    }
+                }
+                TAG_DEFINE_EXTERNAL_IMAGE => {
+                    // Character ID, bitmap format, target width and height.
+                    let file_name = tag
+                        .skip_bytes(8)
+                        .and_then(|_| Self::read_short_string(&mut tag));
+                    if let Ok(file_name) = file_name {
+                        summary.external_images.push(file_name);
+                    }
+                }
+                TAG_DEFINE_EXTERNAL_IMAGE_2 => {
+                    // Character ID, bitmap format, target width and height, export name.
+                    let file_name = tag
+                        .skip_bytes(10)
+                        .and_then(|_| Self::read_short_string(&mut tag))
+                        .and_then(|_| Self::read_short_string(&mut tag));
+                    if let Ok(file_name) = file_name {
+                        summary.external_images.push(file_name);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if !is_end_found {
+            summary
+                .problems
+                .push(String::from("the file is truncated (no end tag found)"));
+        }
+    }
+
+    /// Reads a string that is prefixed with its length (1 byte).
+    fn read_short_string(reader: &mut SwfBitReader) -> Result<String, AppError> {
+        let length = reader.read_u8()? as usize;
+        Ok(String::from_utf8_lossy(reader.read_bytes(length)?).to_string())
+    }
+
+    fn get_tag_name(code: u16) -> String {
+        let name = match code {
+            0 => "End",
+            1 => "ShowFrame",
+            2 => "DefineShape",
+            4 => "PlaceObject",
+            5 => "RemoveObject",
+            6 => "DefineBits",
+            8 => "JPEGTables",
+            9 => "SetBackgroundColor",
+            10 => "DefineFont",
+            11 => "DefineText",
+            12 => "DoAction",
+            13 => "DefineFontInfo",
+            14 => "DefineSound",
+            20 => "DefineBitsLossless",
+            21 => "DefineBitsJPEG2",
+            22 => "DefineShape2",
+            26 => "PlaceObject2",
+            28 => "RemoveObject2",
+            32 => "DefineShape3",
+            33 => "DefineText2",
+            34 => "DefineButton2",
+            35 => "DefineBitsJPEG3",
+            36 => "DefineBitsLossless2",
+            37 => "DefineEditText",
+            39 => "DefineSprite",
+            43 => "FrameLabel",
+            46 => "DefineMorphShape",
+            48 => "DefineFont2",
+            56 => "ExportAssets",
+            57 => "ImportAssets",
+            59 => "DoInitAction",
+            62 => "DefineFontInfo2",
+            69 => "FileAttributes",
+            70 => "PlaceObject3",
+            71 => "ImportAssets2",
+            73 => "DefineFontAlignZones",
+            74 => "CSMTextSettings",
+            75 => "DefineFont3",
+            76 => "SymbolClass",
+            77 => "Metadata",
+            78 => "DefineScalingGrid",
+            82 => "DoABC",
+            83 => "DefineShape4",
+            84 => "DefineMorphShape2",
+            86 => "DefineSceneAndFrameLabelData",
+            87 => "DefineBinaryData",
+            88 => "DefineFontName",
+            90 => "DefineBitsJPEG4",
+            91 => "DefineFont4",
+            1000 => "ExporterInfo",
+            1001 => "DefineExternalImage",
+            1002 => "FontTextureInfo",
+            1003 => "DefineExternalGradient",
+            1004 => "DefineGradientMap",
+            1005 => "DefineCompactedFont",
+            1006 => "DefineExternalSound",
+            1007 => "DefineExternalStreamSound",
+            1008 => "DefineSubImage",
+            1009 => "DefineExternalImage2",
+            _ => return format!("Tag{}", code),
+        };
+
+        String::from(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::managers::swf_manager::tests::{make_movie, make_tag};
+    use crate::misc::test_utils::TempDir;
+
+    /// Returns `.gfx` data with the specified tags.
+    ///
+    /// ## Arguments
+    /// * `tags`: tags of the movie.
+    /// * `has_end_tag`: whether to add the End tag after the tags.
+    fn make_gfx(tags: &[u8], has_end_tag: bool) -> Vec<u8> {
+        let mut data = make_movie(1280, 720, tags);
+        data[..3].copy_from_slice(b"GFX");
+
+        if !has_end_tag {
+            data.truncate(data.len() - 2);
+            let size = (data.len() as u32).to_le_bytes();
+            data[4..SWF_UNCOMPRESSED_HEADER_SIZE].copy_from_slice(&size);
+        }
+
+        data
+    }
+
+    /// Returns a short string (prefixed with its length).
+    fn make_short_string(string: &str) -> Vec<u8> {
+        let mut data = vec![string.len() as u8];
+        data.extend_from_slice(string.as_bytes());
+        data
+    }
+
+    #[test]
+    fn read_summary_lists_tags_and_referenced_files() {
+        let mut import = b"fonts.gfx\0".to_vec();
+        import.extend_from_slice(&[1, 0, 0, 0]); // reserved, no characters
+        let mut tags = make_tag(TAG_IMPORT_ASSETS_2, &import);
+
+        let mut image = vec![1, 0, 0, 0, 64, 0, 32, 0];
+        image.extend_from_slice(&make_short_string("menu_1.dds"));
+        tags.extend_from_slice(&make_tag(TAG_DEFINE_EXTERNAL_IMAGE, &image));
+
+        let mut image = vec![2, 0, 0, 0, 64, 0, 32, 0, 0, 0];
+        image.extend_from_slice(&make_short_string("background"));
+        image.extend_from_slice(&make_short_string("menu_2.dds"));
+        tags.extend_from_slice(&make_tag(TAG_DEFINE_EXTERNAL_IMAGE_2, &image));
+        tags.extend_from_slice(&make_tag(TAG_DEFINE_EXTERNAL_IMAGE_2, &image));
+
+        let dir = TempDir::new("gfx-summary");
+        let path = dir.write_file("menu.gfx", &make_gfx(&tags, true));
+        let path_to_source = dir.write_file("menu.swf", &[0; 4096]);
+
+        let summary = GfxManager::read_summary(
+            &path.to_string_lossy(),
+            Some(&path_to_source.to_string_lossy()),
+        )
+        .unwrap();
+
+        assert!(summary.problems.is_empty());
+        assert_eq!(summary.version, 0x0a);
+        assert!(!summary.is_compressed);
+        assert_eq!(summary.source_file_size, Some(4096));
+        assert_eq!(summary.imported_files, vec![String::from("fonts.gfx")]);
+        assert_eq!(
+            summary.external_images,
+            vec![
+                String::from("menu_1.dds"),
+                String::from("menu_2.dds"),
+                String::from("menu_2.dds")
+            ]
+        );
+        assert_eq!(summary.tag_counts.get("DefineExternalImage2"), Some(&2));
+        assert_eq!(summary.tag_counts.get("End"), Some(&1));
+    }
+
+    #[test]
+    fn read_summary_reports_missing_end_tag() {
+        let tags = make_tag(1, &[]); // ShowFrame
+        let dir = TempDir::new("gfx-no-end");
+        let path = dir.write_file("menu.gfx", &make_gfx(&tags, false));
+
+        let summary = GfxManager::read_summary(&path.to_string_lossy(), None).unwrap();
+
+        assert_eq!(
+            summary.problems,
+            vec![String::from("the file is truncated (no end tag found)")]
+        );
+        assert_eq!(summary.tag_counts.get("ShowFrame"), Some(&1));
+    }
+
+    #[test]
+    fn read_summary_reports_truncated_files() {
+        let tags = make_tag(TAG_IMPORT_ASSETS, b"fonts.gfx\0\0\0");
+        let mut data = make_gfx(&tags, true);
+        data.truncate(data.len() - 6);
+        let dir = TempDir::new("gfx-truncated");
+        let path = dir.write_file("menu.gfx", &data);
+
+        let summary = GfxManager::read_summary(&path.to_string_lossy(), None).unwrap();
+
+        assert_eq!(summary.problems.len(), 2);
+        assert!(summary.problems[0].starts_with("the file is truncated (expected"));
+        assert_eq!(
+            summary.problems[1],
+            "the file is truncated (no end tag found)"
+        );
+        assert!(summary.imported_files.is_empty());
+    }
+
+    #[test]
+    fn read_summary_reports_incomplete_header() {
+        let data = make_gfx(&[], true);
+        let dir = TempDir::new("gfx-header");
+        let path = dir.write_file("menu.gfx", &data[..SWF_UNCOMPRESSED_HEADER_SIZE + 2]);
+
+        let summary = GfxManager::read_summary(&path.to_string_lossy(), None).unwrap();
+
+        assert_eq!(
+            summary.problems.last(),
+            Some(&String::from("the file is truncated (incomplete header)"))
+        );
+    }
+
+    #[test]
+    fn read_summary_rejects_empty_and_non_gfx_files() {
+        let dir = TempDir::new("gfx-invalid");
+
+        let path = dir.write_file("empty.gfx", &[]);
+        assert!(GfxManager::read_summary(&path.to_string_lossy(), None).is_err());
+
+        let path = dir.write_file("movie.gfx", &make_movie(1280, 720, &[]));
+        assert!(GfxManager::read_summary(&path.to_string_lossy(), None).is_err());
+
+        let path = dir.get_path().join("missing.gfx");
+        assert!(GfxManager::read_summary(&path.to_string_lossy(), None).is_err());
+    }
+
+    #[test]
+    fn get_size_description_compares_with_source_file() {
+        let dir = TempDir::new("gfx-size");
+        let path = dir.write_file("menu.gfx", &make_gfx(&[], true));
+        let mut summary = GfxManager::read_summary(&path.to_string_lossy(), None).unwrap();
+
+        summary.file_size = 2048;
+        assert_eq!(summary.get_size_description(), "2 KB");
+
+        summary.source_file_size = Some(4096);
+        assert_eq!(summary.get_size_description(), "2 KB, 50% of the .swf file");
+
+        summary.source_file_size = Some(0);
+        assert_eq!(summary.get_size_description(), "2 KB");
+    }
+}
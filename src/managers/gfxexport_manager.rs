@@ -0,0 +1,278 @@
+// Std.
+use std::path::Path;
+use std::process;
+
+// Custom.
+use super::{build_manifest_manager::BuildManifestManager, gfx_manager::*, swf_manager::SwfImage};
+use crate::misc::{config::GfxExportProfile, error::AppError};
+
+/// Image format that GFxExport uses when no `-i` argument is specified.
+const DEFAULT_IMAGE_FORMAT: &str = "tga";
+/// GFxExport arguments that enable DXT compression of exported images.
+const DXT_COMPRESSION_ARGS: [&str; 4] = ["-d1", "-d1c", "-d3", "-d5"];
+/// GFxExport argument that makes image names unpredictable (images are shared between movies).
+const SHARE_IMAGES_ARG: &str = "-share_images";
+
+/// Result of a finished (or skipped) export.
+pub struct ExportResult {
+    /// `true` if GFxExport was not started because the produced files are up to date.
+    pub is_skipped: bool,
+    /// Summary of the produced `.gfx` file.
+    pub summary: GfxSummary,
+    /// Non-critical errors that occurred during the export.
+    pub warnings: Vec<String>,
+}
+
+/// Runs GFxExport, interprets its arguments and checks files produced by GFxExport.
+pub struct GfxExportManager;
+
+impl GfxExportManager {
+    /// Returns all arguments (except for the path to the `.swf` file) that will be passed to GFxExport.
+    ///
+    /// ## Arguments
+    /// * `path_to_output_dir`: directory where GFxExport should put exported files.
+    /// * `additional_args`: additional GFxExport arguments.
+    pub fn get_export_args(path_to_output_dir: &str, additional_args: &str) -> Vec<String> {
+        let mut args = additional_args
+            .split_ascii_whitespace()
+            .map(String::from)
+            .collect::<Vec<String>>();
+        args.push(String::from("-d")); // specify output directory
+        args.push(path_to_output_dir.to_string());
+
+        args
+    }
+
+    /// Exports the `.swf` file to the output directory (if it was changed since the last export).
+    ///
+    /// ## Arguments
+    /// * `profile`: GFxExport binary, runner and arguments to use.
+    /// * `path_to_swf`: path to the `.swf` file to export.
+    /// * `path_to_output_dir`: directory where GFxExport should put exported files.
+    /// * `images`: images defined in the `.swf` file.
+    /// * `force`: export even if the build manifest says that the produced files are up to date.
+    ///
+    /// ## Return
+    /// `Err(AppError)` if GFxExport failed or produced an invalid `.gfx` file,
+    /// otherwise `Ok(ExportResult)`.
+    pub fn export(
+        profile: &GfxExportProfile,
+        path_to_swf: &str,
+        path_to_output_dir: &str,
+        images: &[SwfImage],
+        force: bool,
+    ) -> Result<ExportResult, AppError> {
+        let swf_file_name = Path::new(path_to_swf).file_stem();
+        if swf_file_name.is_none() {
+            return Err(AppError::new("*.swf file should have a file name"));
+        }
+        let swf_file_name = swf_file_name.unwrap().to_string_lossy();
+
+        let gfx_file_name = format!("{}.gfx", swf_file_name);
+        let path_to_gfx_file = Path::new(path_to_output_dir).join(&gfx_file_name);
+        let path_to_gfx_file = path_to_gfx_file.to_string_lossy();
+
+        let runner = profile
+            .runner
+            .split_ascii_whitespace()
+            .collect::<Vec<&str>>();
+        let args = Self::get_export_args(path_to_output_dir, &profile.additional_gfxexport_args);
+        let export_options = runner
+            .iter()
+            .copied()
+            .chain(args.iter().map(String::as_str))
+            .collect::<Vec<&str>>()
+            .join(" ");
+        let (inputs, inputs_warning) = BuildManifestManager::get_inputs(
+            path_to_swf,
+            &profile.path_to_gfxexport_bin,
+            &export_options,
+        )?;
+        let mut warnings = inputs_warning.into_iter().collect::<Vec<String>>();
+
+        // Skip the export if nothing changed.
+        if !force
+            && BuildManifestManager::is_up_to_date(path_to_output_dir, &swf_file_name, &inputs)
+        {
+            return Ok(ExportResult {
+                is_skipped: true,
+                summary: GfxManager::read_summary(&path_to_gfx_file, Some(path_to_swf))?,
+                warnings,
+            });
+        }
+
+        // Run GFxExport (using the runner if specified).
+        let mut command = match runner.split_first() {
+            Some((runner_bin, runner_args)) => {
+                let mut command = process::Command::new(runner_bin);
+                command
+                    .args(runner_args)
+                    .arg(&profile.path_to_gfxexport_bin);
+                command
+            }
+            None => process::Command::new(&profile.path_to_gfxexport_bin),
+        };
+        let output = command.arg(path_to_swf).args(&args).output();
+        if let Err(e) = output {
+            return Err(AppError::new(&format!(
+                "failed to start GFxExport, error: {}",
+                e
+            )));
+        }
+        let output = output.unwrap();
+
+        // Check the produced .gfx file.
+        let summary = GfxManager::read_summary(&path_to_gfx_file, Some(path_to_swf));
+        if !output.status.success() || summary.is_err() {
+            let mut reason = format!("GFxExport finished with {}", output.status);
+            if let Err(app_error) = summary {
+                reason = format!("{}, {}", reason, app_error.get_message());
+            }
+            return Err(AppError::new(&format!(
+                "{}.\n\nOutput:\n{}{}",
+                reason,
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+        let summary = summary.unwrap();
+
+        // Remember what was produced.
+        let mut output_files = vec![gfx_file_name];
+        output_files.extend(
+            Self::get_expected_image_files(
+                &swf_file_name,
+                images,
+                &profile.additional_gfxexport_args,
+            )
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|file_name| Path::new(path_to_output_dir).join(file_name).exists()),
+        );
+
+        if let Err(app_error) =
+            BuildManifestManager::save(path_to_output_dir, &swf_file_name, &inputs, &output_files)
+        {
+            warnings.push(format!(
+                "failed to update the build manifest, error: {}",
+                app_error.get_message()
+            ));
+        }
+
+        Ok(ExportResult {
+            is_skipped: false,
+            summary,
+            warnings,
+        })
+    }
+
+    /// Returns names of image files that GFxExport will extract from the movie.
+    ///
+    /// ## Arguments
+    /// * `swf_file_name`: name of the `.swf` file without extension.
+    /// * `images`: images defined in the `.swf` file.
+    /// * `additional_args`: additional GFxExport arguments.
+    ///
+    /// ## Return
+    /// `None` if file names can't be predicted (for example, images are shared
+    /// between movies), otherwise names of image files.
+    pub fn get_expected_image_files(
+        swf_file_name: &str,
+        images: &[SwfImage],
+        additional_args: &str,
+    ) -> Option<Vec<String>> {
+        let args = additional_args
+            .split_ascii_whitespace()
+            .collect::<Vec<&str>>();
+
+        if args.contains(&SHARE_IMAGES_ARG) {
+            return None;
+        }
+
+        let extension = Self::get_image_format(&args);
+
+        Some(
+            images
+                .iter()
+                .map(|image| format!("{}_{}.{}", swf_file_name, image.character_id, extension))
+                .collect(),
+        )
+    }
+
+    /// Returns a human-readable report about images that GFxExport will extract from the movie.
+    ///
+    /// ## Arguments
+    /// * `swf_file_name`: name of the `.swf` file without extension.
+    /// * `images`: images defined in the `.swf` file.
+    /// * `additional_args`: additional GFxExport arguments.
+    pub fn get_image_report(
+        swf_file_name: &str,
+        images: &[SwfImage],
+        additional_args: &str,
+    ) -> Vec<String> {
+        if images.is_empty() {
+            return vec![String::from("No images are used.")];
+        }
+
+        let args = additional_args
+            .split_ascii_whitespace()
+            .collect::<Vec<&str>>();
+        let is_compressed = args.iter().any(|arg| DXT_COMPRESSION_ARGS.contains(arg));
+        let file_names = Self::get_expected_image_files(swf_file_name, images, additional_args);
+
+        images
+            .iter()
+            .enumerate()
+            .map(|(index, image)| {
+                let mut line = format!(
+                    "{}, estimated DDS size: {} KB",
+                    image,
+                    (image.estimate_dds_size(is_compressed) + 1023) / 1024
+                );
+
+                if let Some(file_names) = &file_names {
+                    line += &format!(", file: {}", file_names[index]);
+                }
+
+                line
+            })
+            .collect()
+    }
+
+    /// Checks that image files extracted by GFxExport exist.
+    ///
+    /// ## Arguments
+    /// * `path_to_output_dir`: directory where GFxExport puts exported files.
+    /// * `swf_file_name`: name of the `.swf` file without extension.
+    /// * `images`: images defined in the `.swf` file.
+    /// * `additional_args`: additional GFxExport arguments.
+    ///
+    /// ## Return
+    /// `None` if file names can't be predicted, otherwise names of missing files.
+    pub fn get_missing_image_files(
+        path_to_output_dir: &str,
+        swf_file_name: &str,
+        images: &[SwfImage],
+        additional_args: &str,
+    ) -> Option<Vec<String>> {
+        let file_names = Self::get_expected_image_files(swf_file_name, images, additional_args)?;
+
+        Some(
+            file_names
+                .into_iter()
+                .filter(|file_name| !Path::new(path_to_output_dir).join(file_name).exists())
+                .collect(),
+        )
+    }
+
+    /// Returns extension of image files that GFxExport will produce.
+    fn get_image_format(args: &[&str]) -> String {
+        if let Some(position) = args.iter().position(|arg| *arg == "-i") {
+            if let Some(format) = args.get(position + 1) {
+                return format.to_lowercase();
+            }
+        }
+
+        String::from(DEFAULT_IMAGE_FORMAT)
+    }
+}
@@ -1 +1,7 @@
+pub mod build_manifest_manager;
+pub mod cryproject_manager;
+pub mod gfx_manager;
+pub mod gfxexport_manager;
+pub mod session_manager;
+pub mod swf_manager;
 pub mod xml_manager;
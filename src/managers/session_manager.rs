@@ -0,0 +1,212 @@
+// Std.
+use std::fs;
+use std::path::Path;
+
+// External.
+use serde_json::{json, Value};
+
+// Custom.
+use super::xml_manager::XmlConfig;
+use crate::layouts::main_layout::{
+    AlignMode, HAlign, ParameterType, UiParameter, UiRunnable, VAlign,
+};
+use crate::misc::error::AppError;
+
+/// Name of the file (in the config directory) that stores the autosaved session.
+pub const SESSION_FILE_NAME: &str = "session.json";
+
+/// Editing state of the main screen.
+#[derive(Default)]
+pub struct Session {
+    pub path_to_swf: String,
+    pub path_to_gfx_dir: String,
+    pub path_to_xml_dir: String,
+    pub profile: String,
+    pub element: XmlConfig,
+}
+
+/// Autosaves the editing state so that it can be restored after a crash.
+pub struct SessionManager;
+
+impl SessionManager {
+    /// Converts the session to the text that is stored in the session file.
+    pub fn serialize(session: &Session) -> String {
+        let element = &session.element;
+        let runnables_to_json = |runnables: &[UiRunnable]| {
+            runnables
+                .iter()
+                .map(|runnable| {
+                    json!({
+                        "name": runnable.name,
+                        "parameters": runnable
+                            .parameters
+                            .iter()
+                            .map(|parameter| {
+                                json!({
+                                    "name": parameter.name,
+                                    "description": parameter.description,
+                                    "type": parameter.type_.unwrap_or_default().to_string(),
+                                })
+                            })
+                            .collect::<Vec<Value>>(),
+                    })
+                })
+                .collect::<Vec<Value>>()
+        };
+
+        let session = json!({
+            "path_to_swf": session.path_to_swf,
+            "path_to_gfx_dir": session.path_to_gfx_dir,
+            "path_to_xml_dir": session.path_to_xml_dir,
+            "profile": session.profile,
+            "ui_elements_name": element.ui_elements_name,
+            "ui_element_name": element.ui_element_name,
+            "gfx_layer": element.gfx_layer,
+            "align_mode": element.align_mode.to_string(),
+            "scale": element.scale,
+            "maximize": element.maximize,
+            "width": element.width,
+            "height": element.height,
+            "halign": element.halign.to_string(),
+            "valign": element.valign.to_string(),
+            "functions": runnables_to_json(&element.functions),
+            "events": runnables_to_json(&element.events),
+        });
+
+        serde_json::to_string_pretty(&session).unwrap_or_default()
+    }
+
+    /// Writes the serialized session (see `serialize`) to the session file.
+    pub fn save(path_to_file: &Path, content: &str) -> Result<(), AppError> {
+        if let Err(e) = fs::write(path_to_file, content) {
+            return Err(AppError::new(&format!(
+                "failed to write the session file \"{}\", error: {}",
+                path_to_file.to_string_lossy(),
+                e
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Reads the session file.
+    pub fn read(path_to_file: &Path) -> Result<Session, AppError> {
+        let content = fs::read_to_string(path_to_file);
+        if let Err(e) = content {
+            return Err(AppError::new(&format!(
+                "failed to read the session file \"{}\", error: {}",
+                path_to_file.to_string_lossy(),
+                e
+            )));
+        }
+
+        let json = serde_json::from_str::<Value>(&content.unwrap());
+        if let Err(e) = json {
+            return Err(AppError::new(&format!(
+                "failed to parse the session file \"{}\", error: {}",
+                path_to_file.to_string_lossy(),
+                e
+            )));
+        }
+        let json = json.unwrap();
+
+        let get_string = |json: &Value, key: &str| {
+            json.get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let get_bool = |key: &str| json.get(key).and_then(Value::as_bool).unwrap_or_default();
+        let get_u64 = |key: &str| json.get(key).and_then(Value::as_u64).unwrap_or_default();
+        let get_runnables = |key: &str| {
+            json.get(key)
+                .and_then(Value::as_array)
+                .map(|runnables| {
+                    runnables
+                        .iter()
+                        .map(|runnable| UiRunnable {
+                            name: get_string(runnable, "name"),
+                            parameters: runnable
+                                .get("parameters")
+                                .and_then(Value::as_array)
+                                .map(|parameters| {
+                                    parameters
+                                        .iter()
+                                        .map(|parameter| UiParameter {
+                                            name: get_string(parameter, "name"),
+                                            description: get_string(parameter, "description"),
+                                            type_: Some(Self::parse_parameter_type(&get_string(
+                                                parameter, "type",
+                                            ))),
+                                        })
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        Ok(Session {
+            path_to_swf: get_string(&json, "path_to_swf"),
+            path_to_gfx_dir: get_string(&json, "path_to_gfx_dir"),
+            path_to_xml_dir: get_string(&json, "path_to_xml_dir"),
+            profile: get_string(&json, "profile"),
+            element: XmlConfig {
+                ui_elements_name: get_string(&json, "ui_elements_name"),
+                ui_element_name: get_string(&json, "ui_element_name"),
+                gfx_file_name: String::new(),
+                gfx_layer: get_u64("gfx_layer") as usize,
+                align_mode: match get_string(&json, "align_mode").as_str() {
+                    "fullscreen" => AlignMode::Fullscreen,
+                    "fixed" => AlignMode::Fixed,
+                    _ => AlignMode::Dynamic,
+                },
+                scale: get_bool("scale"),
+                maximize: get_bool("maximize"),
+                width: get_u64("width") as u32,
+                height: get_u64("height") as u32,
+                halign: match get_string(&json, "halign").as_str() {
+                    "left" => HAlign::Left,
+                    "right" => HAlign::Right,
+                    _ => HAlign::Center,
+                },
+                valign: match get_string(&json, "valign").as_str() {
+                    "top" => VAlign::Top,
+                    "bottom" => VAlign::Bottom,
+                    _ => VAlign::Center,
+                },
+                functions: get_runnables("functions"),
+                events: get_runnables("events"),
+            },
+        })
+    }
+
+    /// Removes the session file (if it exists).
+    pub fn remove(path_to_file: &Path) -> Result<(), AppError> {
+        if !path_to_file.exists() {
+            return Ok(());
+        }
+
+        if let Err(e) = fs::remove_file(path_to_file) {
+            return Err(AppError::new(&format!(
+                "failed to remove the session file \"{}\", error: {}",
+                path_to_file.to_string_lossy(),
+                e
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn parse_parameter_type(type_: &str) -> ParameterType {
+        match type_ {
+            "int" => ParameterType::Int,
+            "bool" => ParameterType::Bool,
+            "string" => ParameterType::String,
+            "float" => ParameterType::Float,
+            _ => ParameterType::Any,
+        }
+    }
+}
@@ -0,0 +1,1139 @@
+// Std.
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+
+// External.
+use flate2::read::ZlibDecoder;
+
+// Custom.
+use crate::misc::error::AppError;
+
+/// Size of the SWF header part that is never compressed
+/// (signature, version and file length).
+pub const SWF_UNCOMPRESSED_HEADER_SIZE: usize = 8;
+/// SWF stores coordinates in twips (1/20 of a pixel).
+const TWIPS_PER_PIXEL: i32 = 20;
+/// Tag length value that tells that the real length is stored in the next 4 bytes.
+const SWF_LONG_TAG_LENGTH: u16 = 0x3f;
+
+// SWF tag codes.
+const TAG_END: u16 = 0;
+const TAG_DEFINE_BITS: u16 = 6;
+const TAG_DEFINE_BITS_LOSSLESS: u16 = 20;
+const TAG_DEFINE_BITS_JPEG_2: u16 = 21;
+const TAG_DEFINE_BITS_JPEG_3: u16 = 35;
+const TAG_DEFINE_BITS_LOSSLESS_2: u16 = 36;
+const TAG_DEFINE_EDIT_TEXT: u16 = 37;
+const TAG_DEFINE_FONT_2: u16 = 48;
+const TAG_IMPORT_ASSETS: u16 = 57;
+const TAG_IMPORT_ASSETS_2: u16 = 71;
+const TAG_DEFINE_FONT_3: u16 = 75;
+const TAG_DEFINE_BITS_JPEG_4: u16 = 90;
+const TAG_DEFINE_FONT_4: u16 = 91;
+
+// Signatures of image data stored in DefineBitsJPEG tags.
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const GIF_SIGNATURE: &[u8] = b"GIF8";
+// Size of the DDS file header (including magic number).
+const DDS_HEADER_SIZE: u64 = 128;
+
+/// Describes where glyphs of a font come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwfFontSource {
+    /// Glyph outlines are embedded into the movie.
+    Embedded,
+    /// The font is defined without glyphs, the system font will be used.
+    Device,
+    /// The font is imported from another movie (for example, `gfxfontlib.swf`).
+    Imported(String),
+}
+
+impl std::fmt::Display for SwfFontSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwfFontSource::Embedded => write!(f, "embedded"),
+            SwfFontSource::Device => write!(f, "device font"),
+            SwfFontSource::Imported(url) => write!(f, "imported from \"{}\"", url),
+        }
+    }
+}
+
+/// Font used in a `.swf` file.
+#[derive(Debug, Clone)]
+pub struct SwfFont {
+    pub name: String,
+    pub is_bold: bool,
+    pub is_italic: bool,
+    pub source: SwfFontSource,
+    /// Number of text fields that use this font.
+    pub text_field_count: usize,
+    /// Number of text fields that use this font but don't use glyph outlines
+    /// (rendered using a system font).
+    pub device_text_field_count: usize,
+}
+
+impl SwfFont {
+    /// Tells whether the font is in the specified list of font names (case insensitive).
+    pub fn is_in_list(&self, font_names: &[String]) -> bool {
+        font_names
+            .iter()
+            .any(|name| name.trim().eq_ignore_ascii_case(self.name.trim()))
+    }
+}
+
+impl std::fmt::Display for SwfFont {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut style = String::new();
+        if self.is_bold {
+            style += " bold";
+        }
+        if self.is_italic {
+            style += " italic";
+        }
+
+        write!(
+            f,
+            "\"{}\"{} ({}), used by {} text field(s)",
+            self.name, style, self.source, self.text_field_count
+        )?;
+
+        if self.device_text_field_count != 0 {
+            write!(
+                f,
+                ", {} of them use device font rendering",
+                self.device_text_field_count
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwfImageFormat {
+    Jpeg,
+    Png,
+    Gif,
+    Lossless,
+}
+
+impl std::fmt::Display for SwfImageFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SwfImageFormat::Jpeg => "JPEG",
+                SwfImageFormat::Png => "PNG",
+                SwfImageFormat::Gif => "GIF",
+                SwfImageFormat::Lossless => "lossless",
+            }
+        )
+    }
+}
+
+/// Bitmap defined in a `.swf` file.
+#[derive(Debug, Clone)]
+pub struct SwfImage {
+    pub character_id: u16,
+    /// Width in pixels (0 if unknown).
+    pub width: u32,
+    /// Height in pixels (0 if unknown).
+    pub height: u32,
+    pub format: SwfImageFormat,
+    pub has_alpha: bool,
+}
+
+impl SwfImage {
+    /// Estimates the size of this image stored in a DDS file (without mipmaps).
+    ///
+    /// ## Arguments
+    /// * `is_compressed`: whether DXT compression is used (DXT5 for images with alpha,
+    ///   DXT1 otherwise), if `false` 32 bits per pixel are used.
+    pub fn estimate_dds_size(&self, is_compressed: bool) -> u64 {
+        let width = self.width.max(1) as u64;
+        let height = self.height.max(1) as u64;
+
+        let data_size = if is_compressed {
+            // DXT stores 4x4 pixel blocks.
+            let block_count = ((width + 3) / 4) * ((height + 3) / 4);
+            let block_size = if self.has_alpha { 16 } else { 8 };
+            block_count * block_size
+        } else {
+            width * height * 4
+        };
+
+        DDS_HEADER_SIZE + data_size
+    }
+}
+
+impl std::fmt::Display for SwfImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "#{} {}x{} {}",
+            self.character_id, self.width, self.height, self.format
+        )?;
+
+        if self.has_alpha {
+            write!(f, " with alpha")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Information read from a `.swf` file.
+#[derive(Debug, Clone)]
+pub struct SwfInfo {
+    /// Stage width in pixels.
+    pub stage_width: u32,
+    /// Stage height in pixels.
+    pub stage_height: u32,
+    /// Fonts defined in (or imported by) the movie.
+    pub fonts: Vec<SwfFont>,
+    /// Bitmaps defined in the movie.
+    pub images: Vec<SwfImage>,
+}
+
+impl SwfInfo {
+    /// Returns fonts that are not in the specified list of project fonts.
+    ///
+    /// ## Arguments
+    /// * `project_fonts`: names of fonts that are available in the project,
+    ///   if empty no font is considered unknown.
+    pub fn get_unknown_fonts(&self, project_fonts: &[String]) -> Vec<&SwfFont> {
+        if project_fonts.is_empty() {
+            return Vec::new();
+        }
+
+        self.fonts
+            .iter()
+            .filter(|font| !font.is_in_list(project_fonts))
+            .collect()
+    }
+
+    /// Returns a human-readable report about fonts used in the movie.
+    ///
+    /// ## Arguments
+    /// * `project_fonts`: names of fonts that are available in the project.
+    pub fn get_font_report(&self, project_fonts: &[String]) -> Vec<String> {
+        if self.fonts.is_empty() {
+            return vec![String::from("No fonts are used.")];
+        }
+
+        self.fonts
+            .iter()
+            .map(|font| {
+                if !project_fonts.is_empty() && !font.is_in_list(project_fonts) {
+                    format!("{} - WARNING: not a project font", font)
+                } else {
+                    font.to_string()
+                }
+            })
+            .collect()
+    }
+}
+
+pub struct SwfManager;
+
+impl SwfManager {
+    /// Reads the header and tags of the specified `.swf` file.
+    ///
+    /// ## Arguments
+    /// * `path_to_swf`: path to the `.swf` file to read.
+    ///
+    /// ## Return
+    /// `Err(AppError)` if the file is not a valid SWF movie, otherwise `Ok(SwfInfo)`.
+    pub fn read_info(path_to_swf: &str) -> Result<SwfInfo, AppError> {
+        let data = Self::read_movie_data(path_to_swf)?;
+        let mut reader = SwfBitReader::new(&data);
+
+        // Skip signature, version and file length.
+        reader.skip_bytes(SWF_UNCOMPRESSED_HEADER_SIZE)?;
+
+        // Read stage rectangle.
+        let (x_min, x_max, y_min, y_max) = reader.read_rect()?;
+
+        // Skip frame rate and frame count.
+        reader.skip_bytes(4)?;
+
+        let mut info = SwfInfo {
+            stage_width: ((x_max - x_min).max(0) / TWIPS_PER_PIXEL) as u32,
+            stage_height: ((y_max - y_min).max(0) / TWIPS_PER_PIXEL) as u32,
+            fonts: Vec::new(),
+            images: Vec::new(),
+        };
+
+        Self::read_tags(&mut reader, &mut info)?;
+
+        Ok(info)
+    }
+
+    /// Reads all top-level tags of the movie and fills the info.
+    fn read_tags(reader: &mut SwfBitReader, info: &mut SwfInfo) -> Result<(), AppError> {
+        // Fonts by character ID.
+        let mut fonts: HashMap<u16, SwfFont> = HashMap::new();
+        // Font IDs in the order of definition.
+        let mut font_ids: Vec<u16> = Vec::new();
+
+        while !reader.is_at_end() {
+            let (code, body) = reader.read_tag()?;
+            if code == TAG_END {
+                break;
+            }
+
+            let mut tag = SwfBitReader::new(body);
+            match code {
+                TAG_DEFINE_FONT_2 | TAG_DEFINE_FONT_3 => {
+                    let id = tag.read_u16()?;
+                    let flags = tag.read_u8()?;
+                    tag.skip_bytes(1)?; // language code
+                    let name_length = tag.read_u8()? as usize;
+                    let name = Self::bytes_to_string(tag.read_bytes(name_length)?);
+                    let glyph_count = tag.read_u16()?;
+
+                    font_ids.push(id);
+                    fonts.insert(
+                        id,
+                        SwfFont {
+                            name,
+                            is_bold: flags & 0b0000_0001 != 0,
+                            is_italic: flags & 0b0000_0010 != 0,
+                            source: if glyph_count == 0 {
+                                SwfFontSource::Device
+                            } else {
+                                SwfFontSource::Embedded
+                            },
+                            text_field_count: 0,
+                            device_text_field_count: 0,
+                        },
+                    );
+                }
+                TAG_DEFINE_FONT_4 => {
+                    let id = tag.read_u16()?;
+                    let flags = tag.read_u8()?;
+                    let name = tag.read_string()?;
+
+                    font_ids.push(id);
+                    fonts.insert(
+                        id,
+                        SwfFont {
+                            name,
+                            is_bold: flags & 0b0000_0001 != 0,
+                            is_italic: flags & 0b0000_0010 != 0,
+                            source: if flags & 0b0000_0100 != 0 {
+                                SwfFontSource::Embedded
+                            } else {
+                                SwfFontSource::Device
+                            },
+                            text_field_count: 0,
+                            device_text_field_count: 0,
+                        },
+                    );
+                }
+                TAG_IMPORT_ASSETS | TAG_IMPORT_ASSETS_2 => {
+                    let url = tag.read_string()?;
+                    if code == TAG_IMPORT_ASSETS_2 {
+                        tag.skip_bytes(2)?; // reserved
+                    }
+
+                    // We don't know the type of imported characters here,
+                    // so remember them as fonts and keep only used ones later.
+                    let count = tag.read_u16()?;
+                    for _ in 0..count {
+                        let id = tag.read_u16()?;
+                        let name = tag.read_string()?;
+
+                        font_ids.push(id);
+                        fonts.insert(
+                            id,
+                            SwfFont {
+                                name,
+                                is_bold: false,
+                                is_italic: false,
+                                source: SwfFontSource::Imported(url.clone()),
+                                text_field_count: 0,
+                                device_text_field_count: 0,
+                            },
+                        );
+                    }
+                }
+                TAG_DEFINE_EDIT_TEXT => {
+                    tag.skip_bytes(2)?; // character ID
+                    tag.read_rect()?;
+                    let flags = tag.read_u8()?;
+                    let flags2 = tag.read_u8()?;
+
+                    let has_font = flags & 0b0000_0001 != 0;
+                    let use_outlines = flags2 & 0b0000_0001 != 0;
+
+                    if has_font {
+                        let font_id = tag.read_u16()?;
+                        if let Some(font) = fonts.get_mut(&font_id) {
+                            font.text_field_count += 1;
+                            if !use_outlines {
+                                font.device_text_field_count += 1;
+                            }
+                        }
+                    }
+                }
+                TAG_DEFINE_BITS_LOSSLESS | TAG_DEFINE_BITS_LOSSLESS_2 => {
+                    let character_id = tag.read_u16()?;
+                    tag.skip_bytes(1)?; // bitmap format
+                    let width = tag.read_u16()? as u32;
+                    let height = tag.read_u16()? as u32;
+
+                    info.images.push(SwfImage {
+                        character_id,
+                        width,
+                        height,
+                        format: SwfImageFormat::Lossless,
+                        has_alpha: code == TAG_DEFINE_BITS_LOSSLESS_2,
+                    });
+                }
+                TAG_DEFINE_BITS
+                | TAG_DEFINE_BITS_JPEG_2
+                | TAG_DEFINE_BITS_JPEG_3
+                | TAG_DEFINE_BITS_JPEG_4 => {
+                    let character_id = tag.read_u16()?;
+
+                    // JPEG3 and JPEG4 have separate alpha data.
+                    let mut has_separate_alpha = false;
+                    if code == TAG_DEFINE_BITS_JPEG_3 || code == TAG_DEFINE_BITS_JPEG_4 {
+                        let alpha_data_offset = tag.read_u32()? as usize;
+                        if code == TAG_DEFINE_BITS_JPEG_4 {
+                            tag.skip_bytes(2)?; // deblock filter
+                        }
+                        has_separate_alpha = alpha_data_offset < tag.get_remaining_size();
+                    }
+
+                    info.images.push(Self::read_embedded_image(
+                        character_id,
+                        tag.read_bytes(tag.get_remaining_size())?,
+                        has_separate_alpha,
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        // Collect fonts in the order of definition, skip imported characters
+        // that are not used by text fields (those are most likely not fonts).
+        info.fonts = font_ids
+            .iter()
+            .filter_map(|id| fonts.remove(id))
+            .filter(|font| {
+                !matches!(font.source, SwfFontSource::Imported(_)) || font.text_field_count != 0
+            })
+            .collect();
+
+        Ok(())
+    }
+
+    /// Determines format and size of image data stored in DefineBits/DefineBitsJPEG tags
+    /// (may contain JPEG, PNG or GIF data).
+    ///
+    /// ## Arguments
+    /// * `character_id`: ID of the image.
+    /// * `data`: image data.
+    /// * `has_separate_alpha`: whether the tag contains alpha data for the JPEG image.
+    fn read_embedded_image(character_id: u16, data: &[u8], has_separate_alpha: bool) -> SwfImage {
+        if data.starts_with(PNG_SIGNATURE) && data.len() >= 26 {
+            // Size is stored in the IHDR chunk, color types 4 and 6 have alpha.
+            return SwfImage {
+                character_id,
+                width: u32::from_be_bytes([data[16], data[17], data[18], data[19]]),
+                height: u32::from_be_bytes([data[20], data[21], data[22], data[23]]),
+                format: SwfImageFormat::Png,
+                has_alpha: data[25] == 4 || data[25] == 6,
+            };
+        }
+
+        if data.starts_with(GIF_SIGNATURE) && data.len() >= 10 {
+            return SwfImage {
+                character_id,
+                width: u16::from_le_bytes([data[6], data[7]]) as u32,
+                height: u16::from_le_bytes([data[8], data[9]]) as u32,
+                format: SwfImageFormat::Gif,
+                has_alpha: false,
+            };
+        }
+
+        let (width, height) = Self::get_jpeg_size(data).unwrap_or_default();
+        SwfImage {
+            character_id,
+            width,
+            height,
+            format: SwfImageFormat::Jpeg,
+            has_alpha: has_separate_alpha,
+        }
+    }
+
+    /// Looks for the "start of frame" JPEG segment to get the image size.
+    ///
+    /// ## Return
+    /// `None` if the size was not found, otherwise `Some((width, height))`.
+    fn get_jpeg_size(data: &[u8]) -> Option<(u32, u32)> {
+        let mut pos = 0;
+
+        while pos + 1 < data.len() {
+            if data[pos] != 0xFF {
+                pos += 1;
+                continue;
+            }
+
+            let marker = data[pos + 1];
+            pos += 2;
+
+            match marker {
+                // Fill byte.
+                0xFF => {
+                    pos -= 1;
+                    continue;
+                }
+                // Markers without a segment.
+                0x01 | 0xD0..=0xD9 => continue,
+                _ => {}
+            }
+
+            if pos + 2 > data.len() {
+                return None;
+            }
+            let segment_length = u16::from_be_bytes([data[pos], data[pos + 1]]) as usize;
+
+            // SOF0-SOF15 except DHT, JPG and DAC markers.
+            if (0xC0..=0xCF).contains(&marker) && marker != 0xC4 && marker != 0xC8 && marker != 0xCC
+            {
+                if pos + 7 > data.len() {
+                    return None;
+                }
+                let height = u16::from_be_bytes([data[pos + 3], data[pos + 4]]) as u32;
+                let width = u16::from_be_bytes([data[pos + 5], data[pos + 6]]) as u32;
+                return Some((width, height));
+            }
+
+            pos += segment_length;
+        }
+
+        None
+    }
+
+    /// Converts font name bytes to string (font names may have a trailing null character).
+    fn bytes_to_string(bytes: &[u8]) -> String {
+        String::from_utf8_lossy(bytes)
+            .trim_end_matches('\0')
+            .to_string()
+    }
+
+    /// Reads the whole `.swf` (or `.gfx`) file and decompresses it (if needed).
+    ///
+    /// ## Return
+    /// Uncompressed movie data (including the uncompressed header).
+    pub fn read_movie_data(path_to_swf: &str) -> Result<Vec<u8>, AppError> {
+        let data = fs::read(path_to_swf);
+        if let Err(e) = data {
+            return Err(AppError::new(&e.to_string()));
+        }
+        let data = data.unwrap();
+
+        if data.len() < SWF_UNCOMPRESSED_HEADER_SIZE {
+            return Err(AppError::new(&format!(
+                "the file \"{}\" is too small to be a SWF movie",
+                path_to_swf
+            )));
+        }
+
+        match &data[..3] {
+            b"FWS" | b"GFX" => Ok(data),
+            b"CWS" | b"CFX" => {
+                // Everything after the first 8 bytes is compressed using zlib.
+                let mut uncompressed = data[..SWF_UNCOMPRESSED_HEADER_SIZE].to_vec();
+                let mut decoder = ZlibDecoder::new(&data[SWF_UNCOMPRESSED_HEADER_SIZE..]);
+                if let Err(e) = decoder.read_to_end(&mut uncompressed) {
+                    return Err(AppError::new(&format!(
+                        "failed to decompress the SWF movie \"{}\", error: {}",
+                        path_to_swf, e
+                    )));
+                }
+
+                Ok(uncompressed)
+            }
+            b"ZWS" => Err(AppError::new(&format!(
+                "the SWF movie \"{}\" uses LZMA compression which is not supported, \
+                please, publish the movie using zlib compression or no compression",
+                path_to_swf
+            ))),
+            _ => Err(AppError::new(&format!(
+                "the file \"{}\" is not a SWF movie",
+                path_to_swf
+            ))),
+        }
+    }
+}
+
+/// Reads bit and byte values from SWF data.
+pub struct SwfBitReader<'a> {
+    data: &'a [u8],
+    byte_pos: usize,
+    bit_pos: u8,
+}
+
+impl<'a> SwfBitReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            byte_pos: 0,
+            bit_pos: 0,
+        }
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.byte_pos >= self.data.len()
+    }
+
+    /// Returns the number of bytes that were not read yet.
+    fn get_remaining_size(&self) -> usize {
+        self.data.len().saturating_sub(self.byte_pos)
+    }
+
+    pub fn skip_bytes(&mut self, count: usize) -> Result<(), AppError> {
+        self.read_bytes(count)?;
+        Ok(())
+    }
+
+    /// Skips the rest of the current byte if some bits of it were read.
+    fn align_to_byte(&mut self) {
+        if self.bit_pos != 0 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+    }
+
+    pub fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], AppError> {
+        self.align_to_byte();
+
+        if self.byte_pos + count > self.data.len() {
+            return Err(Self::unexpected_end_error());
+        }
+        let bytes = &self.data[self.byte_pos..self.byte_pos + count];
+        self.byte_pos += count;
+
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, AppError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, AppError> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, AppError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a null-terminated string.
+    pub fn read_string(&mut self) -> Result<String, AppError> {
+        self.align_to_byte();
+
+        let length = self.data[self.byte_pos.min(self.data.len())..]
+            .iter()
+            .position(|byte| *byte == 0);
+        if length.is_none() {
+            return Err(Self::unexpected_end_error());
+        }
+        let length = length.unwrap();
+
+        let string = String::from_utf8_lossy(self.read_bytes(length)?).to_string();
+        self.skip_bytes(1)?; // null character
+
+        Ok(string)
+    }
+
+    /// Reads a RECT record.
+    ///
+    /// ## Return
+    /// Values in twips: minimum X, maximum X, minimum Y, maximum Y.
+    pub fn read_rect(&mut self) -> Result<(i32, i32, i32, i32), AppError> {
+        let bits_per_value = self.read_unsigned_bits(5)?;
+        let x_min = self.read_signed_bits(bits_per_value)?;
+        let x_max = self.read_signed_bits(bits_per_value)?;
+        let y_min = self.read_signed_bits(bits_per_value)?;
+        let y_max = self.read_signed_bits(bits_per_value)?;
+        self.align_to_byte();
+
+        Ok((x_min, x_max, y_min, y_max))
+    }
+
+    /// Reads tag header and tag body.
+    ///
+    /// ## Return
+    /// Tag code and tag body.
+    pub fn read_tag(&mut self) -> Result<(u16, &'a [u8]), AppError> {
+        let code_and_length = self.read_u16()?;
+
+        let code = code_and_length >> 6;
+        let mut length = (code_and_length & SWF_LONG_TAG_LENGTH) as usize;
+        if length == SWF_LONG_TAG_LENGTH as usize {
+            length = self.read_u32()? as usize;
+        }
+
+        Ok((code, self.read_bytes(length)?))
+    }
+
+    fn read_unsigned_bits(&mut self, count: u32) -> Result<u32, AppError> {
+        let mut value = 0u32;
+
+        for _ in 0..count {
+            if self.byte_pos >= self.data.len() {
+                return Err(Self::unexpected_end_error());
+            }
+
+            let bit = (self.data[self.byte_pos] >> (7 - self.bit_pos)) & 1;
+            value = (value << 1) | bit as u32;
+
+            self.bit_pos += 1;
+            if self.bit_pos == 8 {
+                self.bit_pos = 0;
+                self.byte_pos += 1;
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn read_signed_bits(&mut self, count: u32) -> Result<i32, AppError> {
+        if count == 0 {
+            return Ok(0);
+        }
+
+        let value = self.read_unsigned_bits(count)?;
+
+        // Extend sign.
+        let shift = 32 - count;
+        Ok(((value << shift) as i32) >> shift)
+    }
+
+    fn unexpected_end_error() -> AppError {
+        AppError::new("unexpected end of SWF data")
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use flate2::{write::ZlibEncoder, Compression};
+    use std::io::Write;
+
+    use crate::misc::test_utils::TempDir;
+
+    /// Writes values as a RECT record (every value uses `bits_per_value` bits).
+    pub(crate) fn make_rect(values: [i32; 4], bits_per_value: u32) -> Vec<u8> {
+        let mut bits = Vec::new();
+        for i in (0..5).rev() {
+            bits.push((bits_per_value >> i) & 1);
+        }
+        for value in values {
+            for i in (0..bits_per_value).rev() {
+                bits.push(((value as u32) >> i) & 1);
+            }
+        }
+
+        bits.chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |byte, (i, bit)| byte | ((*bit as u8) << (7 - i)))
+            })
+            .collect()
+    }
+
+    /// Writes a tag header (short or long) followed by the body.
+    pub(crate) fn make_tag(code: u16, body: &[u8]) -> Vec<u8> {
+        let mut tag = Vec::new();
+        if body.len() < SWF_LONG_TAG_LENGTH as usize {
+            tag.extend_from_slice(&((code << 6) | body.len() as u16).to_le_bytes());
+        } else {
+            tag.extend_from_slice(&((code << 6) | SWF_LONG_TAG_LENGTH).to_le_bytes());
+            tag.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        }
+        tag.extend_from_slice(body);
+        tag
+    }
+
+    /// Returns uncompressed movie data with the specified stage size (in pixels) and tags.
+    pub(crate) fn make_movie(stage_width: i32, stage_height: i32, tags: &[u8]) -> Vec<u8> {
+        let mut body = make_rect(
+            [
+                0,
+                stage_width * TWIPS_PER_PIXEL,
+                0,
+                stage_height * TWIPS_PER_PIXEL,
+            ],
+            16,
+        );
+        body.extend_from_slice(&[0, 30, 1, 0]); // frame rate and frame count
+        body.extend_from_slice(tags);
+        body.extend_from_slice(&make_tag(TAG_END, &[]));
+
+        let mut movie = b"FWS\x0a".to_vec();
+        movie
+            .extend_from_slice(&((SWF_UNCOMPRESSED_HEADER_SIZE + body.len()) as u32).to_le_bytes());
+        movie.extend_from_slice(&body);
+        movie
+    }
+
+    #[test]
+    fn read_signed_bits_extends_sign() {
+        let data = make_rect([-20, 11000, 0, 8000], 15);
+        let mut reader = SwfBitReader::new(&data);
+
+        assert_eq!(reader.read_unsigned_bits(5).unwrap(), 15);
+        assert_eq!(reader.read_signed_bits(15).unwrap(), -20);
+        assert_eq!(reader.read_signed_bits(15).unwrap(), 11000);
+        assert_eq!(reader.read_signed_bits(0).unwrap(), 0);
+        assert_eq!(reader.read_signed_bits(15).unwrap(), 0);
+        assert_eq!(reader.read_signed_bits(15).unwrap(), 8000);
+    }
+
+    #[test]
+    fn read_unsigned_bits_fails_on_truncated_data() {
+        let data = make_rect([0, 11000, 0, 8000], 15);
+        let mut reader = SwfBitReader::new(&data[..4]);
+
+        assert!(reader.read_unsigned_bits(5).is_ok());
+        assert!(reader.read_unsigned_bits(30).is_err());
+    }
+
+    #[test]
+    fn read_rect_reads_signed_values() {
+        let data = make_rect([-20, 11000, 0, 8000], 15);
+        let mut reader = SwfBitReader::new(&data);
+
+        assert_eq!(reader.read_rect().unwrap(), (-20, 11000, 0, 8000));
+        assert!(reader.is_at_end());
+    }
+
+    #[test]
+    fn read_tag_reads_short_and_long_tags() {
+        let long_body = vec![7u8; 100];
+        let mut data = make_tag(TAG_DEFINE_FONT_2, &[1, 2, 3]);
+        data.extend_from_slice(&make_tag(TAG_DEFINE_EDIT_TEXT, &long_body));
+        let mut reader = SwfBitReader::new(&data);
+
+        assert_eq!(
+            reader.read_tag().unwrap(),
+            (TAG_DEFINE_FONT_2, &[1u8, 2, 3][..])
+        );
+        assert_eq!(
+            reader.read_tag().unwrap(),
+            (TAG_DEFINE_EDIT_TEXT, &long_body[..])
+        );
+        assert!(reader.is_at_end());
+    }
+
+    #[test]
+    fn read_tag_fails_on_truncated_body() {
+        let data = make_tag(TAG_DEFINE_FONT_2, &[1, 2, 3]);
+        let mut reader = SwfBitReader::new(&data[..4]);
+
+        assert!(reader.read_tag().is_err());
+    }
+
+    #[test]
+    fn read_info_converts_stage_rect_to_pixels() {
+        let dir = TempDir::new("swf-stage");
+        let path = dir.write_file("stage.swf", &make_movie(1280, 720, &[]));
+
+        let info = SwfManager::read_info(&path.to_string_lossy()).unwrap();
+
+        assert_eq!(info.stage_width, 1280);
+        assert_eq!(info.stage_height, 720);
+    }
+
+    #[test]
+    fn read_info_reads_zlib_compressed_movies() {
+        let movie = make_movie(800, 600, &[]);
+        let mut compressed = b"CWS".to_vec();
+        compressed.extend_from_slice(&movie[3..SWF_UNCOMPRESSED_HEADER_SIZE]);
+        let mut encoder = ZlibEncoder::new(compressed, Compression::default());
+        encoder
+            .write_all(&movie[SWF_UNCOMPRESSED_HEADER_SIZE..])
+            .unwrap();
+        let dir = TempDir::new("swf-compressed");
+        let path = dir.write_file("compressed.swf", &encoder.finish().unwrap());
+
+        let info = SwfManager::read_info(&path.to_string_lossy()).unwrap();
+
+        assert_eq!(info.stage_width, 800);
+        assert_eq!(info.stage_height, 600);
+    }
+
+    #[test]
+    fn read_info_rejects_unsupported_files() {
+        let dir = TempDir::new("swf-unsupported");
+
+        let mut movie = make_movie(800, 600, &[]);
+        movie[0] = b'Z';
+        let lzma_path = dir.write_file("lzma.swf", &movie);
+        assert!(SwfManager::read_info(&lzma_path.to_string_lossy()).is_err());
+
+        let small_path = dir.write_file("small.swf", b"FWS");
+        assert!(SwfManager::read_info(&small_path.to_string_lossy()).is_err());
+
+        let missing_path = dir.get_path().join("missing.swf");
+        assert!(SwfManager::read_info(&missing_path.to_string_lossy()).is_err());
+    }
+
+    /// Reads the tags (without a movie header).
+    fn read_tags(tags: &[u8]) -> SwfInfo {
+        let mut info = SwfInfo {
+            stage_width: 0,
+            stage_height: 0,
+            fonts: Vec::new(),
+            images: Vec::new(),
+        };
+        SwfManager::read_tags(&mut SwfBitReader::new(tags), &mut info).unwrap();
+        info
+    }
+
+    /// Returns a DefineFont2/DefineFont3 tag (only the part that is read).
+    fn make_font_tag(code: u16, id: u16, flags: u8, name: &str, glyph_count: u16) -> Vec<u8> {
+        let mut body = id.to_le_bytes().to_vec();
+        body.extend_from_slice(&[flags, 0, name.len() as u8 + 1]);
+        body.extend_from_slice(name.as_bytes());
+        body.push(0);
+        body.extend_from_slice(&glyph_count.to_le_bytes());
+        make_tag(code, &body)
+    }
+
+    /// Returns a DefineEditText tag (only the part that is read).
+    fn make_edit_text_tag(font_id: Option<u16>, use_outlines: bool) -> Vec<u8> {
+        let mut body = vec![100, 0];
+        body.extend_from_slice(&make_rect([0, 2000, 0, 400], 12));
+        body.push(if font_id.is_some() { 0b0000_0001 } else { 0 });
+        body.push(if use_outlines { 0b0000_0001 } else { 0 });
+        if let Some(font_id) = font_id {
+            body.extend_from_slice(&font_id.to_le_bytes());
+        }
+        make_tag(TAG_DEFINE_EDIT_TEXT, &body)
+    }
+
+    #[test]
+    fn read_tags_reports_embedded_and_device_fonts() {
+        let mut tags = make_font_tag(TAG_DEFINE_FONT_3, 1, 0b0000_0011, "Arial", 95);
+        tags.extend_from_slice(&make_font_tag(TAG_DEFINE_FONT_2, 2, 0, "Verdana", 0));
+        tags.extend_from_slice(&make_edit_text_tag(Some(1), true));
+        tags.extend_from_slice(&make_edit_text_tag(Some(1), false));
+        tags.extend_from_slice(&make_edit_text_tag(Some(2), false));
+        tags.extend_from_slice(&make_edit_text_tag(None, false));
+
+        let info = read_tags(&tags);
+
+        assert_eq!(info.fonts.len(), 2);
+
+        let arial = &info.fonts[0];
+        assert_eq!(arial.name, "Arial");
+        assert!(arial.is_bold && arial.is_italic);
+        assert_eq!(arial.source, SwfFontSource::Embedded);
+        assert_eq!(arial.text_field_count, 2);
+        assert_eq!(arial.device_text_field_count, 1);
+
+        let verdana = &info.fonts[1];
+        assert_eq!(verdana.name, "Verdana");
+        assert!(!verdana.is_bold && !verdana.is_italic);
+        assert_eq!(verdana.source, SwfFontSource::Device);
+        assert_eq!(verdana.text_field_count, 1);
+    }
+
+    #[test]
+    fn read_tags_reads_define_font_4() {
+        let mut body = 5u16.to_le_bytes().to_vec();
+        body.push(0b0000_0110);
+        body.extend_from_slice(b"Roboto\0");
+        let mut tags = make_tag(TAG_DEFINE_FONT_4, &body);
+
+        body[2] = 0b0000_0001;
+        body[0] = 6;
+        tags.extend_from_slice(&make_tag(TAG_DEFINE_FONT_4, &body));
+
+        let info = read_tags(&tags);
+
+        assert_eq!(info.fonts.len(), 2);
+        assert_eq!(info.fonts[0].name, "Roboto");
+        assert!(info.fonts[0].is_italic && !info.fonts[0].is_bold);
+        assert_eq!(info.fonts[0].source, SwfFontSource::Embedded);
+        assert!(info.fonts[1].is_bold);
+        assert_eq!(info.fonts[1].source, SwfFontSource::Device);
+    }
+
+    #[test]
+    fn read_tags_keeps_only_used_imported_characters() {
+        let mut body = b"gfxfontlib.swf\0".to_vec();
+        body.extend_from_slice(&[1, 0]); // reserved
+        body.extend_from_slice(&2u16.to_le_bytes());
+        body.extend_from_slice(&7u16.to_le_bytes());
+        body.extend_from_slice(b"$NormalFont\0");
+        body.extend_from_slice(&8u16.to_le_bytes());
+        body.extend_from_slice(b"SomeClip\0");
+        let mut tags = make_tag(TAG_IMPORT_ASSETS_2, &body);
+        tags.extend_from_slice(&make_edit_text_tag(Some(7), true));
+
+        let info = read_tags(&tags);
+
+        assert_eq!(info.fonts.len(), 1);
+        assert_eq!(info.fonts[0].name, "$NormalFont");
+        assert_eq!(
+            info.fonts[0].source,
+            SwfFontSource::Imported(String::from("gfxfontlib.swf"))
+        );
+    }
+
+    #[test]
+    fn get_unknown_fonts_ignores_case_and_empty_list() {
+        let mut tags = make_font_tag(TAG_DEFINE_FONT_3, 1, 0, "Arial", 1);
+        tags.extend_from_slice(&make_font_tag(TAG_DEFINE_FONT_3, 2, 0, "Verdana", 1));
+        let info = read_tags(&tags);
+
+        assert!(info.get_unknown_fonts(&[]).is_empty());
+
+        let unknown = info.get_unknown_fonts(&[String::from(" arial ")]);
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].name, "Verdana");
+
+        let report = info.get_font_report(&[String::from("ARIAL")]);
+        assert!(!report[0].contains("WARNING"));
+        assert!(report[1].contains("WARNING"));
+    }
+
+    /// Returns a DefineBits* tag with the specified image data.
+    fn make_image_tag(code: u16, id: u16, prefix: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut body = id.to_le_bytes().to_vec();
+        body.extend_from_slice(prefix);
+        body.extend_from_slice(data);
+        make_tag(code, &body)
+    }
+
+    /// Returns minimal JPEG data with the specified size.
+    fn make_jpeg(width: u16, height: u16) -> Vec<u8> {
+        let mut data = vec![0xFF, 0xD8]; // start of image
+        data.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00]); // APP0
+        data.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x0B, 0x08]); // SOF0
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&[0x01, 0x01, 0x11, 0x00]);
+        data.extend_from_slice(&[0xFF, 0xD9]); // end of image
+        data
+    }
+
+    #[test]
+    fn read_tags_reads_lossless_bitmaps() {
+        let size = [3, 64, 0, 32, 0];
+        let mut tags = make_image_tag(TAG_DEFINE_BITS_LOSSLESS, 1, &size, &[0; 4]);
+        tags.extend_from_slice(&make_image_tag(
+            TAG_DEFINE_BITS_LOSSLESS_2,
+            2,
+            &size,
+            &[0; 4],
+        ));
+
+        let info = read_tags(&tags);
+
+        assert_eq!(info.images.len(), 2);
+        assert_eq!(info.images[0].character_id, 1);
+        assert_eq!((info.images[0].width, info.images[0].height), (64, 32));
+        assert_eq!(info.images[0].format, SwfImageFormat::Lossless);
+        assert!(!info.images[0].has_alpha);
+        assert_eq!(info.images[1].character_id, 2);
+        assert!(info.images[1].has_alpha);
+    }
+
+    #[test]
+    fn read_tags_reads_jpeg_bitmaps() {
+        let jpeg = make_jpeg(640, 480);
+        let mut tags = make_image_tag(TAG_DEFINE_BITS_JPEG_2, 1, &[], &jpeg);
+
+        // Alpha data follows the JPEG data.
+        let mut alpha_offset = (jpeg.len() as u32).to_le_bytes().to_vec();
+        let mut jpeg_with_alpha = jpeg.clone();
+        jpeg_with_alpha.extend_from_slice(&[0x78, 0x9C]);
+        tags.extend_from_slice(&make_image_tag(
+            TAG_DEFINE_BITS_JPEG_3,
+            2,
+            &alpha_offset,
+            &jpeg_with_alpha,
+        ));
+
+        // No alpha data, with deblock filter.
+        alpha_offset.extend_from_slice(&[0, 0]);
+        tags.extend_from_slice(&make_image_tag(
+            TAG_DEFINE_BITS_JPEG_4,
+            3,
+            &alpha_offset,
+            &jpeg,
+        ));
+
+        let info = read_tags(&tags);
+
+        assert_eq!(info.images.len(), 3);
+        for image in &info.images {
+            assert_eq!(image.format, SwfImageFormat::Jpeg);
+            assert_eq!((image.width, image.height), (640, 480));
+        }
+        assert!(!info.images[0].has_alpha);
+        assert!(info.images[1].has_alpha);
+        assert!(!info.images[2].has_alpha);
+    }
+
+    #[test]
+    fn read_tags_reads_png_and_gif_data_of_jpeg_tags() {
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend_from_slice(&13u32.to_be_bytes());
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&300u32.to_be_bytes());
+        png.extend_from_slice(&200u32.to_be_bytes());
+        png.extend_from_slice(&[8, 6, 0, 0, 0]); // 8 bits, RGBA
+        let mut tags = make_image_tag(TAG_DEFINE_BITS_JPEG_2, 1, &[], &png);
+
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&16u16.to_le_bytes());
+        gif.extend_from_slice(&8u16.to_le_bytes());
+        tags.extend_from_slice(&make_image_tag(TAG_DEFINE_BITS_JPEG_2, 2, &[], &gif));
+
+        let info = read_tags(&tags);
+
+        assert_eq!(info.images[0].format, SwfImageFormat::Png);
+        assert_eq!((info.images[0].width, info.images[0].height), (300, 200));
+        assert!(info.images[0].has_alpha);
+        assert_eq!(info.images[1].format, SwfImageFormat::Gif);
+        assert_eq!((info.images[1].width, info.images[1].height), (16, 8));
+    }
+
+    #[test]
+    fn estimate_dds_size_uses_dxt_blocks() {
+        let mut image = SwfImage {
+            character_id: 1,
+            width: 100,
+            height: 50,
+            format: SwfImageFormat::Png,
+            has_alpha: false,
+        };
+
+        // 25x13 blocks.
+        assert_eq!(image.estimate_dds_size(true), DDS_HEADER_SIZE + 325 * 8);
+        assert_eq!(
+            image.estimate_dds_size(false),
+            DDS_HEADER_SIZE + 100 * 50 * 4
+        );
+
+        image.has_alpha = true;
+        assert_eq!(image.estimate_dds_size(true), DDS_HEADER_SIZE + 325 * 16);
+    }
+}
@@ -7,7 +7,9 @@ use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::reader::Reader;
 
 // Custom.
-use crate::layouts::main_layout::{HAlign, ParameterType, UiParameter, UiRunnable, VAlign};
+use crate::layouts::main_layout::{
+    AlignMode, HAlign, ParameterType, UiParameter, UiRunnable, VAlign,
+};
 use crate::misc::error::AppError;
 
 #[derive(Default)]
@@ -16,17 +18,50 @@ pub struct XmlConfig {
     pub ui_element_name: String,
     pub gfx_file_name: String,
     pub gfx_layer: usize,
-    pub fullscreen: bool,
+    pub align_mode: AlignMode,
+    pub scale: bool,
+    pub maximize: bool,
+    pub width: u32,
+    pub height: u32,
     pub halign: HAlign,
     pub valign: VAlign,
     pub functions: Vec<UiRunnable>,
     pub events: Vec<UiRunnable>,
 }
 
+/// Line of the difference between two texts.
+pub enum DiffLine {
+    Unchanged(String),
+    Added(String),
+    Removed(String),
+}
+
 pub struct XmlManager;
 
 impl XmlManager {
     pub fn write_config(config: XmlConfig, path_to_config: &str) -> Result<(), AppError> {
+        let result = Self::config_to_string(&config)?;
+
+        // Write result to file.
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path_to_config);
+        if let Err(e) = file {
+            return Err(AppError::new(&e.to_string()));
+        }
+        let mut file = file.unwrap();
+
+        if let Err(e) = file.write_all(result.as_bytes()) {
+            return Err(AppError::new(&e.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the XML that `write_config` writes to the file.
+    pub fn config_to_string(config: &XmlConfig) -> Result<String, AppError> {
         let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 4);
 
         // Write <UIElements> tag.
@@ -59,15 +94,22 @@ impl XmlManager {
 
         // Write <Align> tag.
         let mut element = BytesStart::new("Align");
-        if config.fullscreen {
-            element.push_attribute(("mode", "fullscreen"));
-            element.push_attribute(("scale", "1"));
-            element.push_attribute(("maximize", "1"));
-        } else {
-            element.push_attribute(("mode", "dynamic"));
+        element.push_attribute(("mode", config.align_mode.to_string().as_str()));
+        if config.align_mode != AlignMode::Fullscreen {
             element.push_attribute(("valign", config.valign.to_string().as_str()));
             element.push_attribute(("halign", config.halign.to_string().as_str()));
         }
+        if config.align_mode != AlignMode::Fixed {
+            element.push_attribute(("scale", Self::bool_to_attribute(config.scale)));
+            element.push_attribute(("maximize", Self::bool_to_attribute(config.maximize)));
+        }
+        if config.align_mode.has_fixed_size(config.maximize)
+            && config.width != 0
+            && config.height != 0
+        {
+            element.push_attribute(("width", config.width.to_string().as_str()));
+            element.push_attribute(("height", config.height.to_string().as_str()));
+        }
         if let Err(e) = writer.write_event(Event::Empty(element)) {
             return Err(AppError::new(&e.to_string()));
         }
@@ -218,25 +260,65 @@ impl XmlManager {
             return Err(AppError::new(&e.to_string()));
         }
 
-        let result = writer.into_inner().into_inner();
-
-        // Write result to file.
-        let file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(path_to_config);
-        if let Err(e) = file {
+        let result = String::from_utf8(writer.into_inner().into_inner());
+        if let Err(e) = result {
             return Err(AppError::new(&e.to_string()));
         }
-        let mut file = file.unwrap();
 
-        if let Err(e) = file.write_all(&result) {
-            return Err(AppError::new(&e.to_string()));
+        Ok(result.unwrap())
+    }
+
+    /// Compares two texts line by line.
+    ///
+    /// ## Arguments
+    /// * `old_text`: text to compare with (for example, contents of the existing file).
+    /// * `new_text`: changed text.
+    ///
+    /// ## Return
+    /// Lines of both texts, lines that only exist in `old_text` are marked as removed
+    /// and lines that only exist in `new_text` are marked as added.
+    pub fn get_diff(old_text: &str, new_text: &str) -> Vec<DiffLine> {
+        let old_lines = old_text.lines().collect::<Vec<&str>>();
+        let new_lines = new_text.lines().collect::<Vec<&str>>();
+
+        // Find lengths of the longest common subsequences of line suffixes.
+        let mut common = vec![vec![0usize; new_lines.len() + 1]; old_lines.len() + 1];
+        for old_index in (0..old_lines.len()).rev() {
+            for new_index in (0..new_lines.len()).rev() {
+                common[old_index][new_index] = if old_lines[old_index] == new_lines[new_index] {
+                    common[old_index + 1][new_index + 1] + 1
+                } else {
+                    common[old_index + 1][new_index].max(common[old_index][new_index + 1])
+                };
+            }
         }
 
-        Ok(())
+        let mut diff = Vec::new();
+        let mut old_index = 0;
+        let mut new_index = 0;
+        while old_index < old_lines.len() && new_index < new_lines.len() {
+            if old_lines[old_index] == new_lines[new_index] {
+                diff.push(DiffLine::Unchanged(old_lines[old_index].to_string()));
+                old_index += 1;
+                new_index += 1;
+            } else if common[old_index + 1][new_index] >= common[old_index][new_index + 1] {
+                diff.push(DiffLine::Removed(old_lines[old_index].to_string()));
+                old_index += 1;
+            } else {
+                diff.push(DiffLine::Added(new_lines[new_index].to_string()));
+                new_index += 1;
+            }
+        }
+        for line in &old_lines[old_index..] {
+            diff.push(DiffLine::Removed(line.to_string()));
+        }
+        for line in &new_lines[new_index..] {
+            diff.push(DiffLine::Added(line.to_string()));
+        }
+
+        diff
     }
+
     pub fn read_config(path_to_config: &str) -> Result<XmlConfig, AppError> {
         let reader = Reader::from_file(path_to_config);
         if let Err(e) = reader {
@@ -273,11 +355,29 @@ impl XmlManager {
                     b"Align" => {
                         // Get mode.
                         let mode = Self::get_attribute_value(&event, "mode")?;
-                        if mode == "fullscreen" {
-                            config.fullscreen = true;
-                        } else {
-                            config.fullscreen = false;
+                        match mode.to_lowercase().as_str() {
+                            "fullscreen" => config.align_mode = AlignMode::Fullscreen,
+                            "fixed" => config.align_mode = AlignMode::Fixed,
+                            _ => config.align_mode = AlignMode::Dynamic,
+                        }
+
+                        // Get optional scale and maximize.
+                        if let Ok(scale) = Self::get_attribute_value(&event, "scale") {
+                            config.scale = Self::attribute_to_bool(&scale);
+                        }
+                        if let Ok(maximize) = Self::get_attribute_value(&event, "maximize") {
+                            config.maximize = Self::attribute_to_bool(&maximize);
+                        }
+
+                        // Get optional size.
+                        if let Ok(width) = Self::get_attribute_value(&event, "width") {
+                            config.width = width.parse::<u32>().unwrap_or_default();
+                        }
+                        if let Ok(height) = Self::get_attribute_value(&event, "height") {
+                            config.height = height.parse::<u32>().unwrap_or_default();
+                        }
 
+                        if config.align_mode != AlignMode::Fullscreen {
                             // Get valign.
                             let valign = Self::get_attribute_value(&event, "valign")?;
                             let valign = valign.to_lowercase();
@@ -373,6 +473,18 @@ impl XmlManager {
         Ok(config)
     }
 
+    fn bool_to_attribute(value: bool) -> &'static str {
+        if value {
+            "1"
+        } else {
+            "0"
+        }
+    }
+
+    fn attribute_to_bool(value: &str) -> bool {
+        value == "1" || value.eq_ignore_ascii_case("true")
+    }
+
     fn get_attribute_value(event: &BytesStart, attribute_name: &str) -> Result<String, AppError> {
         // Get attribute data.
         let result = event.try_get_attribute(attribute_name);
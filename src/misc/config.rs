@@ -1,6 +1,7 @@
 // Std.
-use std::fs::create_dir_all;
-use std::path::{Path, PathBuf};
+use std::env;
+use std::fs::{self, create_dir_all, OpenOptions};
+use std::path::{Component, Path, PathBuf};
 
 // External.
 use configparser::ini::Ini;
@@ -8,76 +9,610 @@ use platform_dirs::AppDirs;
 
 // Custom.
 use super::error::*;
+use super::overrides::*;
+use super::project_config::ProjectConfig;
+use crate::managers::cryproject_manager::CryProject;
 
 const CONFIG_DIR_NAME: &str = "CRYENGINE UI Helper";
 const CONFIG_FILE_NAME: &str = "config.ini";
-const CONFIG_GENERAL_SECTION_NAME: &str = "general";
-const CONFIG_PATH_TO_GFXEXPORT_BIN: &str = "path_to_gfxexport_bin";
-const CONFIG_ADDITIONAL_GFXEXPORT_ARGS: &str = "additional_gfxexport_args";
+pub const CONFIG_GENERAL_SECTION_NAME: &str = "general";
+const CONFIG_VERSION: &str = "config_version";
+pub const CONFIG_PATH_TO_GFXEXPORT_BIN: &str = "path_to_gfxexport_bin";
+pub const CONFIG_ADDITIONAL_GFXEXPORT_ARGS: &str = "additional_gfxexport_args";
 const CONFIG_LAST_USED_SWF_DIR: &str = "last_used_swf_dir";
+pub const CONFIG_PROJECT_FONTS: &str = "project_fonts";
+const CONFIG_CURRENT_PROFILE: &str = "current_profile";
+pub const CONFIG_PROFILE_SECTION_PREFIX: &str = "profile.";
+pub const CONFIG_RUNNER: &str = "runner";
+pub const CONFIG_GFX_DIR: &str = "gfx_dir";
+pub const CONFIG_XML_DIR: &str = "xml_dir";
+const CONFIG_LAST_USED_PROFILES_SECTION_NAME: &str = "last_used_profiles";
+const CONFIG_LAST_USED_PROFILE_SWF_DIR_PREFIX: &str = "swf_dir.";
+const CONFIG_LAST_USED_PROFILE_NAME_PREFIX: &str = "profile.";
+const CONFIG_RECENT_FILES_SECTION_NAME: &str = "recent_files";
+const CONFIG_RECENT_FILE_SWF_PREFIX: &str = "swf.";
+const CONFIG_RECENT_FILE_GFX_DIR_PREFIX: &str = "gfx_dir.";
+const CONFIG_RECENT_FILE_XML_DIR_PREFIX: &str = "xml_dir.";
+const CONFIG_RECENT_FILE_ELEMENTS_NAME_PREFIX: &str = "elements_name.";
+const CONFIG_RECENT_FILE_ELEMENT_NAME_PREFIX: &str = "element_name.";
+const CONFIG_RECENT_FILE_PROFILE_PREFIX: &str = "profile.";
+const CONFIG_RECENT_PROJECTS_SECTION_NAME: &str = "recent_projects";
+const CONFIG_RECENT_PROJECT_PREFIX: &str = "project.";
+const CONFIG_WINDOW_SECTION_NAME: &str = "window";
+const CONFIG_WINDOW_WIDTH: &str = "width";
+const CONFIG_WINDOW_HEIGHT: &str = "height";
+const CONFIG_WINDOW_X: &str = "x";
+const CONFIG_WINDOW_Y: &str = "y";
+const CONFIG_ACTIVE_TAB: &str = "active_tab";
+const CONFIG_XML_PREVIEW_SPLIT: &str = "xml_preview_split";
 
-#[derive(Default, Debug)]
-pub struct ApplicationConfig {
+/// Maximum number of remembered recent files and recent projects.
+const MAX_RECENT_ENTRIES: usize = 10;
+
+/// Suffix of the copy of a config file that can't be read.
+const BROKEN_CONFIG_BACKUP_SUFFIX: &str = ".broken.bak";
+/// Version of the config file layout written by this version of the app.
+///
+/// When the layout changes, increment the version and add a migration to `CONFIG_MIGRATIONS`.
+const CURRENT_CONFIG_VERSION: usize = 1;
+/// Functions that upgrade the config file layout, the function at index `N`
+/// upgrades the layout of version `N` to version `N + 1`.
+const CONFIG_MIGRATIONS: [fn(&mut Ini); CURRENT_CONFIG_VERSION] = [migrate_config_to_v1];
+
+const DEFAULT_PROFILE_NAME: &str = "default";
+const DEFAULT_WINDOW_SIZE: (u32, u32) = (900, 700);
+/// Default width of the XML preview (in percent of the tab width).
+const DEFAULT_XML_PREVIEW_SPLIT: u16 = 50;
+/// Limits of the XML preview width (in percent of the tab width).
+pub const XML_PREVIEW_SPLIT_RANGE: (u16, u16) = (20, 80);
+/// Output directory for `.gfx` files when no CRYENGINE project is found.
+const FALLBACK_GFX_DIR: &str = "..";
+/// Output directory for `.xml` files when no CRYENGINE project is found.
+const FALLBACK_XML_DIR: &str = "../UIElements";
+const OUTPUT_DIR_VARIABLE_SWF_DIR: &str = "swf_dir";
+const OUTPUT_DIR_VARIABLE_SWF_STEM: &str = "swf_stem";
+const OUTPUT_DIR_VARIABLE_PROJECT_ROOT: &str = "project_root";
+const OUTPUT_DIR_VARIABLE_PROFILE: &str = "profile";
+
+/// GFxExport setup (for example, for a specific SDK version or texture format).
+#[derive(Debug, Clone)]
+pub struct GfxExportProfile {
+    pub name: String,
     pub path_to_gfxexport_bin: String,
     pub additional_gfxexport_args: String,
+    /// Command used to start GFxExport (for example, `wine`), empty to start GFxExport directly.
+    pub runner: String,
+    /// Template of the output directory for `.gfx` files (see `get_output_dirs`),
+    /// empty to use the directory of the CRYENGINE project.
+    pub gfx_dir: String,
+    /// Template of the output directory for `.xml` files (see `get_output_dirs`),
+    /// empty to use the directory of the CRYENGINE project.
+    pub xml_dir: String,
+}
+
+impl GfxExportProfile {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            path_to_gfxexport_bin: String::new(),
+            additional_gfxexport_args: String::new(),
+            runner: String::new(),
+            gfx_dir: String::new(),
+            xml_dir: String::new(),
+        }
+    }
+
+    /// Returns values with their config keys.
+    pub fn get_values(&self) -> [(&'static str, &String); 5] {
+        [
+            (CONFIG_PATH_TO_GFXEXPORT_BIN, &self.path_to_gfxexport_bin),
+            (
+                CONFIG_ADDITIONAL_GFXEXPORT_ARGS,
+                &self.additional_gfxexport_args,
+            ),
+            (CONFIG_RUNNER, &self.runner),
+            (CONFIG_GFX_DIR, &self.gfx_dir),
+            (CONFIG_XML_DIR, &self.xml_dir),
+        ]
+    }
+
+    /// Returns mutable values with their config keys.
+    pub fn get_values_mut(&mut self) -> [(&'static str, &mut String); 5] {
+        [
+            (
+                CONFIG_PATH_TO_GFXEXPORT_BIN,
+                &mut self.path_to_gfxexport_bin,
+            ),
+            (
+                CONFIG_ADDITIONAL_GFXEXPORT_ARGS,
+                &mut self.additional_gfxexport_args,
+            ),
+            (CONFIG_RUNNER, &mut self.runner),
+            (CONFIG_GFX_DIR, &mut self.gfx_dir),
+            (CONFIG_XML_DIR, &mut self.xml_dir),
+        ]
+    }
+
+    /// Returns output directories for `.gfx` and `.xml` files of the specified `.swf` file.
+    ///
+    /// Directory templates may contain the following variables: `{swf_dir}`, `{swf_stem}`,
+    /// `{project_root}` (root directory of the CRYENGINE project or directory of the project
+    /// config) and `{profile}`, relative paths are relative to the directory of the `.swf` file.
+    ///
+    /// ## Arguments
+    /// * `path_to_swf`: path to the `.swf` file.
+    /// * `cry_project`: CRYENGINE project of the `.swf` file (if found), used
+    ///   when output directories are not specified in the profile.
+    /// * `path_to_project_config`: path to the project config (if found).
+    ///
+    /// ## Return
+    /// `Err(AppError)` if a template can't be resolved, otherwise
+    /// paths to `.gfx` and `.xml` directories.
+    pub fn get_output_dirs(
+        &self,
+        path_to_swf: &Path,
+        cry_project: Option<&CryProject>,
+        path_to_project_config: Option<&Path>,
+    ) -> Result<(PathBuf, PathBuf), AppError> {
+        if path_to_swf.file_stem().is_none() || path_to_swf.parent().is_none() {
+            return Err(AppError::new("*.swf file should have a file name"));
+        }
+        let swf_dir = path_to_swf.parent().unwrap();
+
+        let project_root = match cry_project {
+            Some(cry_project) => Some(cry_project.root_dir.clone()),
+            None => path_to_project_config
+                .and_then(Path::parent)
+                .map(Path::to_path_buf),
+        };
+        let variables = [
+            (
+                OUTPUT_DIR_VARIABLE_SWF_DIR,
+                Some(swf_dir.to_string_lossy().to_string()),
+            ),
+            (
+                OUTPUT_DIR_VARIABLE_SWF_STEM,
+                Some(
+                    path_to_swf
+                        .file_stem()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                ),
+            ),
+            (
+                OUTPUT_DIR_VARIABLE_PROJECT_ROOT,
+                project_root.map(|path| path.to_string_lossy().to_string()),
+            ),
+            (OUTPUT_DIR_VARIABLE_PROFILE, Some(self.name.clone())),
+        ];
+
+        let path_to_gfx_dir = match (self.gfx_dir.is_empty(), cry_project) {
+            (true, Some(cry_project)) => cry_project.get_gfx_dir(),
+            (true, None) => Self::resolve_dir(swf_dir, FALLBACK_GFX_DIR)?,
+            (false, _) => {
+                Self::resolve_dir(swf_dir, &Self::expand_template(&self.gfx_dir, &variables)?)?
+            }
+        };
+        let path_to_xml_dir = match (self.xml_dir.is_empty(), cry_project) {
+            (true, Some(cry_project)) => cry_project.get_xml_dir(),
+            (true, None) => Self::resolve_dir(swf_dir, FALLBACK_XML_DIR)?,
+            (false, _) => {
+                Self::resolve_dir(swf_dir, &Self::expand_template(&self.xml_dir, &variables)?)?
+            }
+        };
+
+        Ok((path_to_gfx_dir, path_to_xml_dir))
+    }
+
+    /// Replaces `{variable}` in the template with values of variables.
+    fn expand_template(
+        template: &str,
+        variables: &[(&str, Option<String>)],
+    ) -> Result<String, AppError> {
+        let mut result = String::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..].find('}');
+            if end.is_none() {
+                return Err(AppError::new(&format!(
+                    "missing \"}}\" in the template \"{}\"",
+                    template
+                )));
+            }
+            let end = start + end.unwrap();
+
+            let name = &rest[start + 1..end];
+            let value = variables.iter().find(|(variable, _)| *variable == name);
+            match value {
+                Some((_, Some(value))) => {
+                    result += &rest[..start];
+                    result += value;
+                }
+                Some((_, None)) => {
+                    return Err(AppError::new(&format!(
+                        "the template \"{}\" uses {{{}}} but no CRYENGINE project \
+                        or project config was found",
+                        template, name
+                    )))
+                }
+                None => {
+                    return Err(AppError::new(&format!(
+                        "unknown variable {{{}}} in the template \"{}\"",
+                        name, template
+                    )))
+                }
+            }
+
+            rest = &rest[end + 1..];
+        }
+        result += rest;
+
+        Ok(result)
+    }
+
+    /// Appends a relative path to the base directory resolving `..` components.
+    fn resolve_dir(base_dir: &Path, relative_dir: &str) -> Result<PathBuf, AppError> {
+        let mut path = base_dir.to_path_buf();
+
+        for component in Path::new(relative_dir).components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !path.pop() {
+                        return Err(AppError::new(&format!(
+                            "the directory \"{}\" points outside of the file system root",
+                            relative_dir
+                        )));
+                    }
+                }
+                _ => path.push(component),
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+/// Previously opened UI element.
+#[derive(Debug, Clone, Default)]
+pub struct RecentFile {
+    pub path_to_swf: String,
+    pub path_to_gfx_dir: String,
+    pub path_to_xml_dir: String,
+    pub ui_elements_name: String,
+    pub ui_element_name: String,
+    pub profile: String,
+}
+
+impl RecentFile {
+    /// Returns text used to display this file in the list of recent files.
+    pub fn get_label(&self) -> String {
+        format!("{} ({})", self.ui_element_name, self.path_to_swf)
+    }
+}
+
+#[derive(Debug)]
+pub struct ApplicationConfig {
+    /// Path to the config file, `None` if no location for the config file was found
+    /// (settings are only kept in memory then).
+    pub path_to_config_file: Option<PathBuf>,
+    /// Whether the config file is read but never saved (its layout is not understood
+    /// by this version of the app and rewriting it would lose values).
+    pub is_read_only: bool,
+    /// Version of the config file layout.
+    pub config_version: usize,
+    /// GFxExport profiles, there is always at least one profile.
+    pub profiles: Vec<GfxExportProfile>,
+    /// Name of the profile that was used last.
+    pub current_profile: String,
+    /// Pairs of "directory of the `.swf` file" - "name of the profile used for this directory".
+    pub last_used_profiles: Vec<(String, String)>,
     pub last_used_swf_dir: String,
+    /// Comma-separated names of fonts that are available in the project.
+    pub project_fonts: String,
+    /// Recently opened UI elements, the most recent first.
+    pub recent_files: Vec<RecentFile>,
+    /// Paths to recently used `.cryproject` files or project configs, the most recent first.
+    pub recent_projects: Vec<String>,
+    /// Size of the main window.
+    pub window_size: (u32, u32),
+    /// Position of the main window, `None` to center the window.
+    pub window_position: Option<(i32, i32)>,
+    /// Name of the tab that was opened last.
+    pub active_tab: String,
+    /// Width of the XML preview (in percent of the tab width).
+    pub xml_preview_split: u16,
+    /// Project config found for the last selected `.swf` file, its values
+    /// take precedence over values of the user config (it's never saved to the user config).
+    pub project_config: Option<ProjectConfig>,
+    /// Values of `CRYUI_*` environment variables, they take precedence over config files.
+    pub env_overrides: SettingOverrides,
+    /// Values of command line flags, they take precedence over environment variables.
+    pub cli_overrides: SettingOverrides,
+    /// Values (section, key, value) of the config file that are not used
+    /// by this version of the app, they are saved back as is.
+    pub unknown_values: Vec<(String, String, Option<String>)>,
+}
+
+impl Default for ApplicationConfig {
+    fn default() -> Self {
+        Self {
+            path_to_config_file: None,
+            is_read_only: false,
+            config_version: CURRENT_CONFIG_VERSION,
+            profiles: vec![GfxExportProfile::new(DEFAULT_PROFILE_NAME)],
+            current_profile: String::from(DEFAULT_PROFILE_NAME),
+            last_used_profiles: Vec::new(),
+            last_used_swf_dir: String::new(),
+            project_fonts: String::new(),
+            recent_files: Vec::new(),
+            recent_projects: Vec::new(),
+            window_size: DEFAULT_WINDOW_SIZE,
+            window_position: None,
+            active_tab: String::new(),
+            xml_preview_split: DEFAULT_XML_PREVIEW_SPLIT,
+            project_config: None,
+            env_overrides: SettingOverrides::default(),
+            cli_overrides: SettingOverrides::default(),
+            unknown_values: Vec::new(),
+        }
+    }
 }
 
 impl ApplicationConfig {
     pub fn new() -> Self {
-        let mut app_config = ApplicationConfig::default();
+        // Find where the config file is stored.
+        let mut app_config = match Self::find_config_file_path() {
+            Ok(path) => Self::load(path),
+            Err(app_error) => {
+                // Non-critical error, run with default settings.
+                println!(
+                    "WARNING: {}, settings will not be saved",
+                    app_error.get_message()
+                );
+                ApplicationConfig::default()
+            }
+        };
+
+        app_config.env_overrides = SettingOverrides::from_env();
+
+        app_config
+    }
+
+    /// Reads settings from the config file, creates the file if it does not exist
+    /// and upgrades it if it has an older layout.
+    ///
+    /// ## Arguments
+    /// * `config_path`: path to the config file.
+    fn load(config_path: PathBuf) -> Self {
+        let mut app_config = ApplicationConfig {
+            path_to_config_file: Some(config_path.clone()),
+            ..Default::default()
+        };
 
         // Try reading config from .ini file.
-        let mut config = Ini::new();
-        let config_path = Self::get_config_file_path();
+        let mut config = Ini::new_cs();
         let map = config.load(&config_path);
-        if map.is_err() {
-            // No file found, create a new file.
-            if let Err(e) = app_config.save() {
+        if let Err(e) = map {
+            if !config_path.exists() {
+                // No file found, create a new file.
+                if let Err(e) = app_config.save() {
+                    // Non-critical error.
+                    print!("WARNING: {}", AppError::new(&e.to_string()));
+                }
+                return app_config;
+            }
+
+            // Don't overwrite a file that can't be read, the user may want to fix it.
+            println!(
+                "WARNING: failed to read the config file \"{}\", error: {}, \
+                running with default settings that will not be saved",
+                config_path.to_string_lossy(),
+                e
+            );
+            if let Err(app_error) =
+                Self::backup_config_file(&config_path, BROKEN_CONFIG_BACKUP_SUFFIX)
+            {
                 // Non-critical error.
-                print!("WARNING: {}", AppError::new(&e.to_string()));
+                println!("WARNING: {}", app_error);
             }
+            app_config.path_to_config_file = None;
             return app_config;
         }
 
-        let mut some_values_were_empty = false;
+        // Upgrade older layouts (keeping a copy of the old file).
+        let mut should_resave = false;
+        match Self::read_config_version(&config) {
+            Some(CURRENT_CONFIG_VERSION) => {}
+            Some(config_version) if config_version < CURRENT_CONFIG_VERSION => {
+                if let Err(app_error) =
+                    Self::backup_config_file(&config_path, &format!(".v{}.bak", config_version))
+                {
+                    // Non-critical error.
+                    println!("WARNING: {}", app_error);
+                }
+                for migration in CONFIG_MIGRATIONS[config_version..].iter() {
+                    migration(&mut config);
+                }
+                should_resave = true;
+            }
+            config_version => {
+                // Don't overwrite a layout that is not understood, values would be lost.
+                let (suffix, reason) = match config_version {
+                    Some(version) => (
+                        format!(".v{}.bak", version),
+                        format!(
+                            "was created by a newer version of the app \
+                            (config version {}, supported version {})",
+                            version, CURRENT_CONFIG_VERSION
+                        ),
+                    ),
+                    None => (
+                        String::from(BROKEN_CONFIG_BACKUP_SUFFIX),
+                        String::from("has an invalid config version"),
+                    ),
+                };
+                println!(
+                    "WARNING: the config file \"{}\" {}, its settings will be used \
+                    but changes will not be saved",
+                    config_path.to_string_lossy(),
+                    reason
+                );
+                if let Err(app_error) = Self::backup_config_file(&config_path, &suffix) {
+                    // Non-critical error.
+                    println!("WARNING: {}", app_error);
+                }
+                app_config.is_read_only = true;
+            }
+        }
 
         // Read config.
-        // Read path to GFxExport binary.
-        let path_to_gfxexport_bin =
-            config.get(CONFIG_GENERAL_SECTION_NAME, CONFIG_PATH_TO_GFXEXPORT_BIN);
-        if let Some(path) = path_to_gfxexport_bin {
-            if Path::new(&path).exists() {
-                app_config.path_to_gfxexport_bin = path;
+        // Read GFxExport profiles.
+        let mut profiles = config
+            .sections()
+            .into_iter()
+            .filter_map(|section| {
+                section
+                    .strip_prefix(CONFIG_PROFILE_SECTION_PREFIX)
+                    .map(|name| Self::read_profile(&config, &section, name))
+            })
+            .collect::<Vec<GfxExportProfile>>();
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        if !profiles.is_empty() {
+            app_config.profiles = profiles;
+        }
+
+        // Missing files are kept so that the path is not lost if a drive is not mounted.
+        for profile in app_config.profiles.iter() {
+            if !profile.path_to_gfxexport_bin.is_empty()
+                && !Path::new(&profile.path_to_gfxexport_bin).exists()
+            {
+                println!(
+                    "WARNING: GFxExport of the profile \"{}\" is not found at \"{}\"",
+                    profile.name, profile.path_to_gfxexport_bin
+                );
             }
-        } else {
-            some_values_were_empty = true;
         }
 
-        // Read additional GFxExport arguments.
-        let additional_gfxexport_args = config.get(
-            CONFIG_GENERAL_SECTION_NAME,
-            CONFIG_ADDITIONAL_GFXEXPORT_ARGS,
-        );
-        if let Some(args) = additional_gfxexport_args {
-            app_config.additional_gfxexport_args = args;
+        // Read current profile (it may be defined in a project config).
+        let current_profile = config.get(CONFIG_GENERAL_SECTION_NAME, CONFIG_CURRENT_PROFILE);
+        if let Some(name) = current_profile {
+            app_config.current_profile = name;
         } else {
-            some_values_were_empty = true;
+            app_config.current_profile = app_config.profiles[0].name.clone();
+        }
+
+        // Read profiles used for directories of .swf files.
+        let mut index = 0;
+        while let (Some(swf_dir), Some(profile_name)) = (
+            config.get(
+                CONFIG_LAST_USED_PROFILES_SECTION_NAME,
+                &format!("{}{}", CONFIG_LAST_USED_PROFILE_SWF_DIR_PREFIX, index),
+            ),
+            config.get(
+                CONFIG_LAST_USED_PROFILES_SECTION_NAME,
+                &format!("{}{}", CONFIG_LAST_USED_PROFILE_NAME_PREFIX, index),
+            ),
+        ) {
+            app_config.last_used_profiles.push((swf_dir, profile_name));
+            index += 1;
         }
 
         // Read last used directory path for .swf files.
         let last_used_swf_dir = config.get(CONFIG_GENERAL_SECTION_NAME, CONFIG_LAST_USED_SWF_DIR);
         if let Some(path) = last_used_swf_dir {
-            if Path::new(&path).exists() {
-                app_config.last_used_swf_dir = path;
+            // Kept so that the path is not lost if a drive is not mounted.
+            if !path.is_empty() && !Path::new(&path).exists() {
+                println!(
+                    "WARNING: last used directory for .swf files is not found at \"{}\"",
+                    path
+                );
             }
-        } else {
-            some_values_were_empty = true;
+            app_config.last_used_swf_dir = path;
+        }
+
+        // Read project fonts.
+        let project_fonts = config.get(CONFIG_GENERAL_SECTION_NAME, CONFIG_PROJECT_FONTS);
+        if let Some(fonts) = project_fonts {
+            app_config.project_fonts = fonts;
+        }
+
+        // Read window geometry and UI state.
+        if let (Ok(Some(width)), Ok(Some(height))) = (
+            config.getuint(CONFIG_WINDOW_SECTION_NAME, CONFIG_WINDOW_WIDTH),
+            config.getuint(CONFIG_WINDOW_SECTION_NAME, CONFIG_WINDOW_HEIGHT),
+        ) {
+            if width != 0 && height != 0 {
+                app_config.window_size = (width as u32, height as u32);
+            }
+        }
+        if let (Ok(Some(x)), Ok(Some(y))) = (
+            config.getint(CONFIG_WINDOW_SECTION_NAME, CONFIG_WINDOW_X),
+            config.getint(CONFIG_WINDOW_SECTION_NAME, CONFIG_WINDOW_Y),
+        ) {
+            app_config.window_position = Some((x as i32, y as i32));
+        }
+        if let Some(tab) = config.get(CONFIG_WINDOW_SECTION_NAME, CONFIG_ACTIVE_TAB) {
+            app_config.active_tab = tab;
+        }
+        if let Ok(Some(split)) =
+            config.getuint(CONFIG_WINDOW_SECTION_NAME, CONFIG_XML_PREVIEW_SPLIT)
+        {
+            app_config.xml_preview_split =
+                (split as u16).clamp(XML_PREVIEW_SPLIT_RANGE.0, XML_PREVIEW_SPLIT_RANGE.1);
+        }
+
+        // Read recent files.
+        let mut index = 0;
+        while let Some(path_to_swf) = config.get(
+            CONFIG_RECENT_FILES_SECTION_NAME,
+            &format!("{}{}", CONFIG_RECENT_FILE_SWF_PREFIX, index),
+        ) {
+            let get_value = |prefix: &str| {
+                config
+                    .get(
+                        CONFIG_RECENT_FILES_SECTION_NAME,
+                        &format!("{}{}", prefix, index),
+                    )
+                    .unwrap_or_default()
+            };
+            app_config.recent_files.push(RecentFile {
+                path_to_swf,
+                path_to_gfx_dir: get_value(CONFIG_RECENT_FILE_GFX_DIR_PREFIX),
+                path_to_xml_dir: get_value(CONFIG_RECENT_FILE_XML_DIR_PREFIX),
+                ui_elements_name: get_value(CONFIG_RECENT_FILE_ELEMENTS_NAME_PREFIX),
+                ui_element_name: get_value(CONFIG_RECENT_FILE_ELEMENT_NAME_PREFIX),
+                profile: get_value(CONFIG_RECENT_FILE_PROFILE_PREFIX),
+            });
+            index += 1;
+        }
+
+        // Read recent projects.
+        let mut index = 0;
+        while let Some(path_to_project) = config.get(
+            CONFIG_RECENT_PROJECTS_SECTION_NAME,
+            &format!("{}{}", CONFIG_RECENT_PROJECT_PREFIX, index),
+        ) {
+            app_config.recent_projects.push(path_to_project);
+            index += 1;
+        }
+
+        // Forget files that were removed.
+        let were_recent_entries_removed = app_config.remove_missing_recent_entries();
+
+        // Keep values that this version of the app does not know about.
+        app_config.unknown_values = Self::read_unknown_values(&config);
+        for (section, key, _) in app_config.unknown_values.iter() {
+            println!(
+                "WARNING: unknown value \"{}\" in the section \"{}\" of the config file",
+                key, section
+            );
         }
 
         // Resave if needed.
-        if some_values_were_empty {
-            // Create a new file with all values filled.
+        if should_resave || were_recent_entries_removed {
             if let Err(e) = app_config.save() {
                 // Non-critical error.
                 print!("WARNING: {}", AppError::new(&e.to_string()));
@@ -87,19 +622,25 @@ impl ApplicationConfig {
         app_config
     }
 
+    /// Saves settings to the config file (does nothing if the config is only kept in memory
+    /// or is read-only).
     pub fn save(&self) -> Result<(), AppError> {
-        let mut config = Ini::new();
+        if self.path_to_config_file.is_none() || self.is_read_only {
+            return Ok(());
+        }
+
+        let mut config = Ini::new_cs();
 
         config.setstr(
             CONFIG_GENERAL_SECTION_NAME,
-            CONFIG_PATH_TO_GFXEXPORT_BIN,
-            Some(&self.path_to_gfxexport_bin),
+            CONFIG_VERSION,
+            Some(&self.config_version.to_string()),
         );
 
         config.setstr(
             CONFIG_GENERAL_SECTION_NAME,
-            CONFIG_ADDITIONAL_GFXEXPORT_ARGS,
-            Some(&self.additional_gfxexport_args),
+            CONFIG_CURRENT_PROFILE,
+            Some(&self.current_profile),
         );
 
         config.setstr(
@@ -108,39 +649,935 @@ impl ApplicationConfig {
             Some(&self.last_used_swf_dir),
         );
 
-        if let Err(e) = config.write(Self::get_config_file_path()) {
+        config.setstr(
+            CONFIG_GENERAL_SECTION_NAME,
+            CONFIG_PROJECT_FONTS,
+            Some(&self.project_fonts),
+        );
+
+        for profile in self.profiles.iter() {
+            let section = format!("{}{}", CONFIG_PROFILE_SECTION_PREFIX, profile.name);
+            for (key, value) in profile.get_values() {
+                config.setstr(&section, key, Some(value));
+            }
+        }
+
+        for (index, (swf_dir, profile_name)) in self.last_used_profiles.iter().enumerate() {
+            config.setstr(
+                CONFIG_LAST_USED_PROFILES_SECTION_NAME,
+                &format!("{}{}", CONFIG_LAST_USED_PROFILE_SWF_DIR_PREFIX, index),
+                Some(swf_dir),
+            );
+            config.setstr(
+                CONFIG_LAST_USED_PROFILES_SECTION_NAME,
+                &format!("{}{}", CONFIG_LAST_USED_PROFILE_NAME_PREFIX, index),
+                Some(profile_name),
+            );
+        }
+
+        for (index, recent_file) in self.recent_files.iter().enumerate() {
+            for (prefix, value) in [
+                (CONFIG_RECENT_FILE_SWF_PREFIX, &recent_file.path_to_swf),
+                (
+                    CONFIG_RECENT_FILE_GFX_DIR_PREFIX,
+                    &recent_file.path_to_gfx_dir,
+                ),
+                (
+                    CONFIG_RECENT_FILE_XML_DIR_PREFIX,
+                    &recent_file.path_to_xml_dir,
+                ),
+                (
+                    CONFIG_RECENT_FILE_ELEMENTS_NAME_PREFIX,
+                    &recent_file.ui_elements_name,
+                ),
+                (
+                    CONFIG_RECENT_FILE_ELEMENT_NAME_PREFIX,
+                    &recent_file.ui_element_name,
+                ),
+                (CONFIG_RECENT_FILE_PROFILE_PREFIX, &recent_file.profile),
+            ] {
+                config.setstr(
+                    CONFIG_RECENT_FILES_SECTION_NAME,
+                    &format!("{}{}", prefix, index),
+                    Some(value),
+                );
+            }
+        }
+
+        for (index, path_to_project) in self.recent_projects.iter().enumerate() {
+            config.setstr(
+                CONFIG_RECENT_PROJECTS_SECTION_NAME,
+                &format!("{}{}", CONFIG_RECENT_PROJECT_PREFIX, index),
+                Some(path_to_project),
+            );
+        }
+
+        config.setstr(
+            CONFIG_WINDOW_SECTION_NAME,
+            CONFIG_WINDOW_WIDTH,
+            Some(&self.window_size.0.to_string()),
+        );
+        config.setstr(
+            CONFIG_WINDOW_SECTION_NAME,
+            CONFIG_WINDOW_HEIGHT,
+            Some(&self.window_size.1.to_string()),
+        );
+        if let Some((x, y)) = self.window_position {
+            config.setstr(
+                CONFIG_WINDOW_SECTION_NAME,
+                CONFIG_WINDOW_X,
+                Some(&x.to_string()),
+            );
+            config.setstr(
+                CONFIG_WINDOW_SECTION_NAME,
+                CONFIG_WINDOW_Y,
+                Some(&y.to_string()),
+            );
+        }
+        config.setstr(
+            CONFIG_WINDOW_SECTION_NAME,
+            CONFIG_ACTIVE_TAB,
+            Some(&self.active_tab),
+        );
+        config.setstr(
+            CONFIG_WINDOW_SECTION_NAME,
+            CONFIG_XML_PREVIEW_SPLIT,
+            Some(&self.xml_preview_split.to_string()),
+        );
+
+        for (section, key, value) in self.unknown_values.iter() {
+            config.set(section, key, value.clone());
+        }
+
+        if let Err(e) = config.write(self.path_to_config_file.as_ref().unwrap()) {
             return Err(AppError::new(&e.to_string()));
         }
 
         Ok(())
     }
 
-    pub fn get_config_file_path() -> PathBuf {
-        #[cfg(any(windows, unix))]
+    /// Looks for a project config for the specified `.swf` file
+    /// (the previously loaded project config is discarded).
+    pub fn load_project_config(&mut self, path_to_swf: &Path) -> Result<(), AppError> {
+        self.project_config = None;
+        self.project_config = ProjectConfig::find(path_to_swf)?;
+
+        Ok(())
+    }
+
+    /// Returns names of profiles from the user config and the project config.
+    pub fn get_profile_names(&self) -> Vec<String> {
+        let mut names = self
+            .profiles
+            .iter()
+            .map(|profile| profile.name.clone())
+            .collect::<Vec<String>>();
+
+        if let Some(project_config) = &self.project_config {
+            for profile in project_config.profiles.iter() {
+                if !names.contains(&profile.name) {
+                    names.push(profile.name.clone());
+                }
+            }
+        }
+
+        names.sort();
+        names
+    }
+
+    /// Returns the profile with values from the project config applied over the user config
+    /// (and values of environment variables and command line flags applied over them).
+    pub fn get_profile(&self, name: &str) -> Option<GfxExportProfile> {
+        let overrides = self
+            .project_config
+            .as_ref()
+            .and_then(|project_config| project_config.get_profile_overrides(name));
+
+        let mut profile = match self.profiles.iter().find(|profile| profile.name == name) {
+            Some(profile) => profile.clone(),
+            None => {
+                overrides?;
+                GfxExportProfile::new(name)
+            }
+        };
+
+        if let Some(overrides) = overrides {
+            overrides.apply(&mut profile);
+        }
+        self.env_overrides.apply(&mut profile);
+        self.cli_overrides.apply(&mut profile);
+
+        Some(profile)
+    }
+
+    /// Saves profile values to the user config (except for values specified in the project config,
+    /// environment variables or command line flags).
+    pub fn set_profile(&mut self, profile: &GfxExportProfile) {
+        if !self.profiles.iter().any(|user| user.name == profile.name) {
+            self.profiles.push(GfxExportProfile::new(&profile.name));
+        }
+        let user_profile = self
+            .profiles
+            .iter()
+            .find(|user| user.name == profile.name)
+            .unwrap()
+            .clone();
+
+        // Keep user values that are hidden by overrides.
+        let mut updated_profile = profile.clone();
+        for ((key, value), (_, user_value)) in updated_profile
+            .get_values_mut()
+            .into_iter()
+            .zip(user_profile.get_values())
+        {
+            if self.get_value_origin(&profile.name, key).is_some() {
+                *value = user_value.clone();
+            }
+        }
+
+        *self
+            .profiles
+            .iter_mut()
+            .find(|user| user.name == profile.name)
+            .unwrap() = updated_profile;
+    }
+
+    /// Returns the profile that was used last.
+    pub fn get_current_profile(&self) -> GfxExportProfile {
+        self.get_profile(&self.current_profile)
+            .unwrap_or_else(|| self.profiles[0].clone())
+    }
+
+    /// Returns the profile to use for the specified directory (see `get_profile_name_for_swf_dir`),
+    /// the current profile is returned if the profile specified by overrides does not exist.
+    pub fn get_profile_for_swf_dir(&self, swf_dir: &str) -> GfxExportProfile {
+        self.get_profile(&self.get_profile_name_for_swf_dir(swf_dir).0)
+            .unwrap_or_else(|| self.get_current_profile())
+    }
+
+    /// Returns the name of the profile to use for the specified directory and where it comes from.
+    ///
+    /// The profile is taken from: command line flags, environment variables, the profile
+    /// last used for the directory, the default profile of the project config, the current profile.
+    pub fn get_profile_name_for_swf_dir(&self, swf_dir: &str) -> (String, String) {
+        if let Some((name, origin)) = self
+            .cli_overrides
+            .get(SETTING_PROFILE)
+            .or_else(|| self.env_overrides.get(SETTING_PROFILE))
+        {
+            return (name.clone(), origin.clone());
+        }
+
+        if let Some((_, name)) = self
+            .last_used_profiles
+            .iter()
+            .find(|(dir, name)| dir == swf_dir && self.get_profile(name).is_some())
+        {
+            return (
+                name.clone(),
+                format!("user config (last used for \"{}\")", swf_dir),
+            );
+        }
+
+        if let Some(project_config) = &self.project_config {
+            if let Some(name) = &project_config.default_profile {
+                if self.get_profile(name).is_some() {
+                    return (
+                        name.clone(),
+                        format!(
+                            "project config {}",
+                            project_config.path_to_file.to_string_lossy()
+                        ),
+                    );
+                }
+            }
+        }
+
+        (
+            self.get_current_profile().name,
+            String::from("user config (current profile)"),
+        )
+    }
+
+    /// Returns project fonts from overrides, the project config or the user config.
+    pub fn get_project_fonts(&self) -> String {
+        self.get_overridden_value(CONFIG_PROJECT_FONTS)
+            .or_else(|| {
+                self.project_config
+                    .as_ref()
+                    .and_then(|project_config| project_config.project_fonts.clone())
+            })
+            .unwrap_or_else(|| self.project_fonts.clone())
+    }
+
+    /// Saves project fonts to the user config (unless they are overridden).
+    pub fn set_project_fonts(&mut self, fonts: &str) {
+        let is_overridden = self.get_overridden_value(CONFIG_PROJECT_FONTS).is_some()
+            || self
+                .project_config
+                .as_ref()
+                .map(|project_config| project_config.project_fonts.is_some())
+                .unwrap_or(false);
+
+        if !is_overridden {
+            self.project_fonts = fonts.to_string();
+        }
+    }
+
+    /// Returns effective values of settings for `.swf` files in the specified directory
+    /// as triples of "setting name" - "value" - "where the value comes from".
+    pub fn get_effective_settings(&self, swf_dir: &str) -> Vec<(String, String, String)> {
+        let (profile_name, profile_origin) = self.get_profile_name_for_swf_dir(swf_dir);
+        let mut settings = Vec::new();
+
+        let profile = match self.get_profile(&profile_name) {
+            Some(profile) => {
+                settings.push((
+                    String::from(SETTING_PROFILE),
+                    profile_name.clone(),
+                    profile_origin,
+                ));
+                profile
+            }
+            None => {
+                settings.push((
+                    String::from(SETTING_PROFILE),
+                    profile_name.clone(),
+                    format!("{}, unknown profile", profile_origin),
+                ));
+                GfxExportProfile::new(&profile_name)
+            }
+        };
+
+        for (key, value) in profile.get_values() {
+            let origin = self
+                .get_value_origin(&profile_name, key)
+                .unwrap_or_else(|| {
+                    if self.profiles.iter().any(|user| user.name == profile_name) {
+                        String::from("user config")
+                    } else {
+                        String::from("default")
+                    }
+                });
+            settings.push((key.to_string(), value.clone(), origin));
+        }
+
+        let fonts_origin = match (
+            self.cli_overrides
+                .get(CONFIG_PROJECT_FONTS)
+                .or_else(|| self.env_overrides.get(CONFIG_PROJECT_FONTS)),
+            &self.project_config,
+        ) {
+            (Some((_, origin)), _) => origin.clone(),
+            (None, Some(project_config)) if project_config.project_fonts.is_some() => format!(
+                "project config {}",
+                project_config.path_to_file.to_string_lossy()
+            ),
+            _ => String::from("user config"),
+        };
+        settings.push((
+            String::from(CONFIG_PROJECT_FONTS),
+            self.get_project_fonts(),
+            fonts_origin,
+        ));
+
+        settings
+    }
+
+    /// Returns where the overridden profile value comes from,
+    /// `None` if the value is taken from the user config.
+    fn get_value_origin(&self, profile_name: &str, key: &str) -> Option<String> {
+        if let Some((_, origin)) = self
+            .cli_overrides
+            .get(key)
+            .or_else(|| self.env_overrides.get(key))
         {
-            let app_dirs = AppDirs::new(Some(CONFIG_DIR_NAME), true).unwrap_or_else(|| {
-                panic!(
-                    "An error occurred at [{}, {}]: can't read user dirs.",
-                    file!(),
-                    line!(),
+            return Some(origin.clone());
+        }
+
+        let project_config = self.project_config.as_ref()?;
+        project_config
+            .get_profile_overrides(profile_name)
+            .and_then(|overrides| overrides.get(key))
+            .map(|_| {
+                format!(
+                    "project config {}",
+                    project_config.path_to_file.to_string_lossy()
                 )
-            });
+            })
+    }
+
+    /// Returns the value of the setting from command line flags or environment variables.
+    fn get_overridden_value(&self, setting: &str) -> Option<String> {
+        self.cli_overrides
+            .get(setting)
+            .or_else(|| self.env_overrides.get(setting))
+            .map(|(value, _)| value.clone())
+    }
+
+    /// Makes the specified profile current and remembers it for the specified directory.
+    pub fn set_last_used_profile(&mut self, swf_dir: &str, profile_name: &str) {
+        self.current_profile = profile_name.to_string();
+
+        match self
+            .last_used_profiles
+            .iter_mut()
+            .find(|(dir, _)| dir == swf_dir)
+        {
+            Some((_, name)) => *name = profile_name.to_string(),
+            None => self
+                .last_used_profiles
+                .push((swf_dir.to_string(), profile_name.to_string())),
+        }
+    }
+
+    /// Puts the file to the top of the recent files list
+    /// (replacing the entry for the same element of the same `.swf` file).
+    pub fn add_recent_file(&mut self, recent_file: RecentFile) {
+        self.recent_files.retain(|file| {
+            file.path_to_swf != recent_file.path_to_swf
+                || file.ui_element_name != recent_file.ui_element_name
+        });
+        self.recent_files.insert(0, recent_file);
+        self.recent_files.truncate(MAX_RECENT_ENTRIES);
+    }
+
+    /// Puts the project file to the top of the recent projects list.
+    pub fn add_recent_project(&mut self, path_to_project: &str) {
+        self.recent_projects.retain(|path| path != path_to_project);
+        self.recent_projects.insert(0, path_to_project.to_string());
+        self.recent_projects.truncate(MAX_RECENT_ENTRIES);
+    }
+
+    /// Removes recent files and recent projects that no longer exist.
+    ///
+    /// ## Return
+    /// `true` if some entries were removed, `false` otherwise.
+    pub fn remove_missing_recent_entries(&mut self) -> bool {
+        let entry_count = self.recent_files.len() + self.recent_projects.len();
+
+        self.recent_files
+            .retain(|file| Path::new(&file.path_to_swf).is_file());
+        self.recent_projects
+            .retain(|path| Path::new(path).is_file());
+
+        entry_count != self.recent_files.len() + self.recent_projects.len()
+    }
+
+    /// Splits comma-separated font names (like `project_fonts`) into a list.
+    pub fn parse_font_list(fonts: &str) -> Vec<String> {
+        fonts
+            .split(',')
+            .map(|font| font.trim().to_string())
+            .filter(|font| !font.is_empty())
+            .collect()
+    }
+
+    fn read_profile(config: &Ini, section: &str, name: &str) -> GfxExportProfile {
+        let mut profile = GfxExportProfile::new(name);
+
+        if let Some(path) = config.get(section, CONFIG_PATH_TO_GFXEXPORT_BIN) {
+            profile.path_to_gfxexport_bin = path;
+        }
+        if let Some(args) = config.get(section, CONFIG_ADDITIONAL_GFXEXPORT_ARGS) {
+            profile.additional_gfxexport_args = args;
+        }
+        if let Some(runner) = config.get(section, CONFIG_RUNNER) {
+            profile.runner = runner;
+        }
+        if let Some(dir) = config.get(section, CONFIG_GFX_DIR) {
+            profile.gfx_dir = dir;
+        }
+        if let Some(dir) = config.get(section, CONFIG_XML_DIR) {
+            profile.xml_dir = dir;
+        }
+
+        profile
+    }
+
+    /// Returns the version of the config file layout, files without the version
+    /// are version 0 (no profiles) or version 1 (profiles but no version).
+    ///
+    /// ## Return
+    /// `None` if the stored version is not a number.
+    fn read_config_version(config: &Ini) -> Option<usize> {
+        match config.get(CONFIG_GENERAL_SECTION_NAME, CONFIG_VERSION) {
+            Some(version) => version.trim().parse().ok(),
+            None => {
+                if config
+                    .sections()
+                    .iter()
+                    .any(|section| section.starts_with(CONFIG_PROFILE_SECTION_PREFIX))
+                {
+                    Some(1)
+                } else {
+                    Some(0)
+                }
+            }
+        }
+    }
+
+    /// Copies the config file before it's rewritten.
+    ///
+    /// ## Arguments
+    /// * `config_path`: path to the config file.
+    /// * `suffix`: suffix to add to the file name of the copy
+    ///   (for example, `.v<version>.bak`).
+    fn backup_config_file(config_path: &Path, suffix: &str) -> Result<(), AppError> {
+        let mut backup_path = config_path.as_os_str().to_os_string();
+        backup_path.push(suffix);
+
+        if let Err(e) = fs::copy(config_path, &backup_path) {
+            return Err(AppError::new(&format!(
+                "failed to backup the config file to \"{}\", error: {}",
+                backup_path.to_string_lossy(),
+                e
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns values (section, key, value) that are not used by this version of the app.
+    fn read_unknown_values(config: &Ini) -> Vec<(String, String, Option<String>)> {
+        let mut unknown_values = Vec::new();
+
+        for (section, values) in config.get_map_ref().iter() {
+            let known_keys: &[&str] = if section == CONFIG_GENERAL_SECTION_NAME {
+                &[
+                    CONFIG_VERSION,
+                    CONFIG_CURRENT_PROFILE,
+                    CONFIG_LAST_USED_SWF_DIR,
+                    CONFIG_PROJECT_FONTS,
+                ]
+            } else if section.starts_with(CONFIG_PROFILE_SECTION_PREFIX) {
+                &[
+                    CONFIG_PATH_TO_GFXEXPORT_BIN,
+                    CONFIG_ADDITIONAL_GFXEXPORT_ARGS,
+                    CONFIG_RUNNER,
+                    CONFIG_GFX_DIR,
+                    CONFIG_XML_DIR,
+                ]
+            } else if section == CONFIG_WINDOW_SECTION_NAME {
+                &[
+                    CONFIG_WINDOW_WIDTH,
+                    CONFIG_WINDOW_HEIGHT,
+                    CONFIG_WINDOW_X,
+                    CONFIG_WINDOW_Y,
+                    CONFIG_ACTIVE_TAB,
+                    CONFIG_XML_PREVIEW_SPLIT,
+                ]
+            } else if section == CONFIG_LAST_USED_PROFILES_SECTION_NAME
+                || section == CONFIG_RECENT_FILES_SECTION_NAME
+                || section == CONFIG_RECENT_PROJECTS_SECTION_NAME
+            {
+                // Lists are rewritten on save.
+                continue;
+            } else {
+                &[]
+            };
+
+            for (key, value) in values.iter() {
+                if !known_keys.contains(&key.as_str()) {
+                    unknown_values.push((section.clone(), key.clone(), value.clone()));
+                }
+            }
+        }
+
+        unknown_values.sort();
+        unknown_values
+    }
+
+    /// Returns the directory of the config file, `None` if the config is only kept in memory.
+    pub fn get_config_dir(&self) -> Option<&Path> {
+        self.path_to_config_file.as_deref().and_then(Path::parent)
+    }
+
+    /// Returns path to the config file for messages.
+    pub fn get_config_file_path(&self) -> String {
+        match &self.path_to_config_file {
+            Some(path) => path.to_string_lossy().to_string(),
+            None => String::from("<not saved, no location for the config file was found>"),
+        }
+    }
+
+    /// Returns path to the config file in the user config directory or, if the user
+    /// config directory can't be used, path to the portable config next to the executable.
+    fn find_config_file_path() -> Result<PathBuf, AppError> {
+        let user_config_error = match Self::get_user_config_file_path() {
+            Ok(path) => return Ok(path),
+            Err(app_error) => app_error,
+        };
+
+        match Self::get_portable_config_file_path() {
+            Ok(path) => {
+                println!(
+                    "WARNING: {}, using portable config \"{}\"",
+                    user_config_error.get_message(),
+                    path.to_string_lossy()
+                );
+                Ok(path)
+            }
+            Err(app_error) => Err(AppError::new(&format!(
+                "{}, {}",
+                user_config_error.get_message(),
+                app_error.get_message()
+            ))),
+        }
+    }
+
+    fn get_user_config_file_path() -> Result<PathBuf, AppError> {
+        #[cfg(any(windows, unix))]
+        {
+            let app_dirs = AppDirs::new(Some(CONFIG_DIR_NAME), true);
+            if app_dirs.is_none() {
+                return Err(AppError::new(
+                    "failed to determine the user config directory",
+                ));
+            }
 
-            let mut config_path = app_dirs.config_dir;
+            let mut config_path = app_dirs.unwrap().config_dir;
 
             // Create directory if not exists.
             if !config_path.exists() {
                 if let Err(e) = create_dir_all(&config_path) {
-                    panic!("An error occurred at [{}, {}]: {:?}", file!(), line!(), e);
+                    return Err(AppError::new(&format!(
+                        "failed to create the user config directory \"{}\", error: {}",
+                        config_path.to_string_lossy(),
+                        e
+                    )));
                 }
             }
 
             config_path.push(CONFIG_FILE_NAME);
-            config_path
+            Ok(config_path)
         }
         #[cfg(not(any(windows, unix)))]
         {
             compile_error!("Client is not implemented for this OS.");
         }
     }
+
+    /// Returns path to `config.ini` next to the executable if this file can be written.
+    fn get_portable_config_file_path() -> Result<PathBuf, AppError> {
+        let path_to_exe = env::current_exe();
+        if let Err(e) = path_to_exe {
+            return Err(AppError::new(&format!(
+                "failed to get the path to the executable, error: {}",
+                e
+            )));
+        }
+        let path_to_exe = path_to_exe.unwrap();
+
+        let config_path = path_to_exe
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(CONFIG_FILE_NAME);
+
+        if let Err(e) = Self::check_config_file_writable(&config_path) {
+            return Err(AppError::new(&format!(
+                "failed to use the portable config \"{}\", error: {}",
+                config_path.to_string_lossy(),
+                e
+            )));
+        }
+
+        Ok(config_path)
+    }
+
+    /// Makes sure the config file can be written without modifying an existing file
+    /// or creating a new one (an empty config file would be read as the oldest layout).
+    fn check_config_file_writable(config_path: &Path) -> std::io::Result<()> {
+        if config_path.exists() {
+            return OpenOptions::new()
+                .append(true)
+                .open(config_path)
+                .map(|_| ());
+        }
+
+        let mut probe_path = config_path.as_os_str().to_os_string();
+        probe_path.push(".probe");
+        OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&probe_path)?;
+        fs::remove_file(&probe_path)
+    }
+}
+
+/// Moves the only GFxExport setup from the general section to the default profile.
+fn migrate_config_to_v1(config: &mut Ini) {
+    let profile_section = format!("{}{}", CONFIG_PROFILE_SECTION_PREFIX, DEFAULT_PROFILE_NAME);
+
+    for key in [
+        CONFIG_PATH_TO_GFXEXPORT_BIN,
+        CONFIG_ADDITIONAL_GFXEXPORT_ARGS,
+    ] {
+        if let Some(value) = config.remove_key(CONFIG_GENERAL_SECTION_NAME, key) {
+            config.set(&profile_section, key, value);
+        }
+    }
+
+    config.setstr(
+        CONFIG_GENERAL_SECTION_NAME,
+        CONFIG_CURRENT_PROFILE,
+        Some(DEFAULT_PROFILE_NAME),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::project_config::ProfileOverrides;
+    use crate::misc::test_utils::TempDir;
+
+    fn get_variables() -> [(&'static str, Option<String>); 3] {
+        [
+            (OUTPUT_DIR_VARIABLE_SWF_STEM, Some(String::from("menu"))),
+            (OUTPUT_DIR_VARIABLE_PROFILE, Some(String::from("dds"))),
+            (OUTPUT_DIR_VARIABLE_PROJECT_ROOT, None),
+        ]
+    }
+
+    #[test]
+    fn expand_template_replaces_variables() {
+        assert_eq!(
+            GfxExportProfile::expand_template(
+                "out/{profile}/{swf_stem}_{profile}",
+                &get_variables()
+            )
+            .unwrap(),
+            "out/dds/menu_dds"
+        );
+        assert_eq!(
+            GfxExportProfile::expand_template("../UIElements", &get_variables()).unwrap(),
+            "../UIElements"
+        );
+    }
+
+    #[test]
+    fn expand_template_fails_on_invalid_templates() {
+        // Unknown variable.
+        assert!(GfxExportProfile::expand_template("{swf_name}", &get_variables()).is_err());
+        // No value (no project found).
+        assert!(GfxExportProfile::expand_template("{project_root}/UI", &get_variables()).is_err());
+        // Not closed.
+        assert!(GfxExportProfile::expand_template("out/{profile", &get_variables()).is_err());
+    }
+
+    /// Reads the config from the `.ini` text.
+    fn read_config(text: &str) -> Ini {
+        let mut config = Ini::new_cs();
+        config.read(text.to_string()).unwrap();
+        config
+    }
+
+    #[test]
+    fn read_config_version_reads_stored_version() {
+        assert_eq!(
+            ApplicationConfig::read_config_version(&read_config("[general]\nconfig_version = 1\n")),
+            Some(1)
+        );
+        assert_eq!(
+            ApplicationConfig::read_config_version(&read_config("[general]\nconfig_version = 5\n")),
+            Some(5)
+        );
+        assert_eq!(
+            ApplicationConfig::read_config_version(&read_config(
+                "[general]\nconfig_version = abc\n"
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn read_config_version_detects_unversioned_layouts() {
+        // Before profiles.
+        assert_eq!(
+            ApplicationConfig::read_config_version(&read_config(
+                "[general]\npath_to_gfxexport_bin = gfxexport.exe\n"
+            )),
+            Some(0)
+        );
+        // Profiles without the version.
+        assert_eq!(
+            ApplicationConfig::read_config_version(&read_config(
+                "[general]\ncurrent_profile = default\n[profile.default]\nrunner = \n"
+            )),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn migrations_move_gfxexport_settings_to_default_profile() {
+        let mut config = read_config(
+            "[general]\npath_to_gfxexport_bin = C:\\gfxexport.exe\n\
+            additional_gfxexport_args = -i DDS\nlast_used_swf_dir = C:\\ui\n",
+        );
+
+        for migration in CONFIG_MIGRATIONS.iter() {
+            migration(&mut config);
+        }
+
+        let profile_section = format!("{}{}", CONFIG_PROFILE_SECTION_PREFIX, DEFAULT_PROFILE_NAME);
+        assert_eq!(
+            config.get(&profile_section, CONFIG_PATH_TO_GFXEXPORT_BIN),
+            Some(String::from("C:\\gfxexport.exe"))
+        );
+        assert_eq!(
+            config.get(&profile_section, CONFIG_ADDITIONAL_GFXEXPORT_ARGS),
+            Some(String::from("-i DDS"))
+        );
+        assert_eq!(
+            config.get(CONFIG_GENERAL_SECTION_NAME, CONFIG_PATH_TO_GFXEXPORT_BIN),
+            None
+        );
+        assert_eq!(
+            config.get(CONFIG_GENERAL_SECTION_NAME, CONFIG_CURRENT_PROFILE),
+            Some(String::from(DEFAULT_PROFILE_NAME))
+        );
+        assert_eq!(
+            config.get(CONFIG_GENERAL_SECTION_NAME, CONFIG_LAST_USED_SWF_DIR),
+            Some(String::from("C:\\ui"))
+        );
+        assert_eq!(ApplicationConfig::read_config_version(&config), Some(1));
+    }
+
+    #[test]
+    fn load_upgrades_older_configs() {
+        let temp_dir = TempDir::new("config-upgrade");
+        let config_path = temp_dir.write_file(
+            "config.ini",
+            b"[general]\npath_to_gfxexport_bin = gfxexport.exe\n",
+        );
+
+        let app_config = ApplicationConfig::load(config_path.clone());
+
+        assert!(!app_config.is_read_only);
+        assert_eq!(
+            app_config.profiles[0].path_to_gfxexport_bin,
+            "gfxexport.exe"
+        );
+        assert!(temp_dir.get_path().join("config.ini.v0.bak").exists());
+        let config = read_config(&fs::read_to_string(&config_path).unwrap());
+        assert_eq!(
+            ApplicationConfig::read_config_version(&config),
+            Some(CURRENT_CONFIG_VERSION)
+        );
+    }
+
+    #[test]
+    fn load_does_not_rewrite_configs_of_newer_versions() {
+        let temp_dir = TempDir::new("config-newer");
+        let text = format!(
+            "[general]\nconfig_version = {}\nlast_used_swf_dir = /not/mounted/ui\n\
+            [profile.default]\nrunner = wine\n",
+            CURRENT_CONFIG_VERSION + 1
+        );
+        let config_path = temp_dir.write_file("config.ini", text.as_bytes());
+
+        let app_config = ApplicationConfig::load(config_path.clone());
+
+        assert!(app_config.is_read_only);
+        assert_eq!(app_config.profiles[0].runner, "wine");
+        // Missing directories are kept.
+        assert_eq!(app_config.last_used_swf_dir, "/not/mounted/ui");
+        app_config.save().unwrap();
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), text);
+        assert!(temp_dir
+            .get_path()
+            .join(format!("config.ini.v{}.bak", CURRENT_CONFIG_VERSION + 1))
+            .exists());
+    }
+
+    #[test]
+    fn load_does_not_rewrite_configs_with_invalid_version() {
+        let temp_dir = TempDir::new("config-invalid-version");
+        let text = "[general]\nconfig_version = abc\n";
+        let config_path = temp_dir.write_file("config.ini", text.as_bytes());
+
+        let app_config = ApplicationConfig::load(config_path.clone());
+
+        assert!(app_config.is_read_only);
+        app_config.save().unwrap();
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), text);
+        assert!(temp_dir
+            .get_path()
+            .join(format!("config.ini{}", BROKEN_CONFIG_BACKUP_SUFFIX))
+            .exists());
+    }
+
+    /// Returns overrides parsed from command line flags.
+    fn get_overrides(args: &[&str]) -> SettingOverrides {
+        let args = args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>();
+        SettingOverrides::from_args(&args).unwrap().0
+    }
+
+    #[test]
+    fn get_profile_applies_overrides_in_order() {
+        let app_config = ApplicationConfig {
+            profiles: vec![GfxExportProfile {
+                name: String::from(DEFAULT_PROFILE_NAME),
+                path_to_gfxexport_bin: String::from("user.exe"),
+                additional_gfxexport_args: String::from("user args"),
+                runner: String::from("user runner"),
+                gfx_dir: String::from("user gfx"),
+                xml_dir: String::from("user xml"),
+            }],
+            project_fonts: String::from("user fonts"),
+            project_config: Some(ProjectConfig {
+                project_fonts: Some(String::from("project fonts")),
+                profiles: vec![ProfileOverrides {
+                    name: String::from(DEFAULT_PROFILE_NAME),
+                    additional_gfxexport_args: Some(String::from("project args")),
+                    runner: Some(String::from("project runner")),
+                    gfx_dir: Some(String::from("project gfx")),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            env_overrides: get_overrides(&[
+                "--runner",
+                "env runner",
+                "--gfx-dir",
+                "env gfx",
+                "--project-fonts",
+                "env fonts",
+            ]),
+            cli_overrides: get_overrides(&["--gfx-dir", "cli gfx"]),
+            ..Default::default()
+        };
+
+        let profile = app_config.get_profile(DEFAULT_PROFILE_NAME).unwrap();
+
+        assert_eq!(profile.path_to_gfxexport_bin, "user.exe");
+        assert_eq!(profile.additional_gfxexport_args, "project args");
+        assert_eq!(profile.runner, "env runner");
+        assert_eq!(profile.gfx_dir, "cli gfx");
+        assert_eq!(profile.xml_dir, "user xml");
+        assert_eq!(app_config.get_project_fonts(), "env fonts");
+    }
+
+    #[test]
+    fn fresh_portable_config_is_not_upgraded() {
+        let temp_dir = TempDir::new("config-portable");
+        let config_path = temp_dir.get_path().join(CONFIG_FILE_NAME);
+
+        ApplicationConfig::check_config_file_writable(&config_path).unwrap();
+        assert_eq!(fs::read_dir(temp_dir.get_path()).unwrap().count(), 0);
+
+        let app_config = ApplicationConfig::load(config_path.clone());
+
+        assert!(!app_config.is_read_only);
+        assert_eq!(fs::read_dir(temp_dir.get_path()).unwrap().count(), 1);
+        let config = read_config(&fs::read_to_string(&config_path).unwrap());
+        assert_eq!(
+            ApplicationConfig::read_config_version(&config),
+            Some(CURRENT_CONFIG_VERSION)
+        );
+    }
 }
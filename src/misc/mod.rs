@@ -1,4 +1,8 @@
 pub mod config;
 pub mod error;
+pub mod overrides;
+pub mod project_config;
 pub mod style;
+#[cfg(test)]
+pub mod test_utils;
 pub mod theme;
@@ -0,0 +1,124 @@
+// Std.
+use std::env;
+
+// Custom.
+use super::config::*;
+use super::error::*;
+
+/// Name of the setting that selects the profile.
+pub const SETTING_PROFILE: &str = "profile";
+
+/// Pairs of "environment variable" - "setting name" (setting names match config keys).
+const ENV_VARIABLES: [(&str, &str); 7] = [
+    ("CRYUI_PROFILE", SETTING_PROFILE),
+    ("CRYUI_GFXEXPORT", CONFIG_PATH_TO_GFXEXPORT_BIN),
+    ("CRYUI_GFXEXPORT_ARGS", CONFIG_ADDITIONAL_GFXEXPORT_ARGS),
+    ("CRYUI_RUNNER", CONFIG_RUNNER),
+    ("CRYUI_GFX_DIR", CONFIG_GFX_DIR),
+    ("CRYUI_XML_DIR", CONFIG_XML_DIR),
+    ("CRYUI_PROJECT_FONTS", CONFIG_PROJECT_FONTS),
+];
+
+/// Pairs of "command line flag" - "setting name" (setting names match config keys).
+const CLI_FLAGS: [(&str, &str); 7] = [
+    ("--profile", SETTING_PROFILE),
+    ("--gfxexport", CONFIG_PATH_TO_GFXEXPORT_BIN),
+    ("--gfxexport-args", CONFIG_ADDITIONAL_GFXEXPORT_ARGS),
+    ("--runner", CONFIG_RUNNER),
+    ("--gfx-dir", CONFIG_GFX_DIR),
+    ("--xml-dir", CONFIG_XML_DIR),
+    ("--project-fonts", CONFIG_PROJECT_FONTS),
+];
+
+/// Setting values specified outside of config files (environment variables or command line flags),
+/// they take precedence over values from the project config and the user config.
+#[derive(Debug, Default)]
+pub struct SettingOverrides {
+    /// Triples of "setting name" - "value" - "where the value comes from".
+    values: Vec<(&'static str, String, String)>,
+}
+
+impl SettingOverrides {
+    /// Reads `CRYUI_*` environment variables, empty variables are ignored.
+    pub fn from_env() -> Self {
+        let mut overrides = Self::default();
+
+        for (variable, setting) in ENV_VARIABLES {
+            if let Ok(value) = env::var(variable) {
+                if !value.is_empty() {
+                    overrides.values.push((
+                        setting,
+                        value,
+                        format!("environment variable {}", variable),
+                    ));
+                }
+            }
+        }
+
+        overrides
+    }
+
+    /// Takes setting flags (like `--gfxexport <path>`) from command line arguments.
+    ///
+    /// ## Return
+    /// Overrides and arguments that are not setting flags, or `Err(AppError)`
+    /// if a flag has no value.
+    pub fn from_args(args: &[String]) -> Result<(Self, Vec<String>), AppError> {
+        let mut overrides = Self::default();
+        let mut other_args = Vec::new();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let flag = CLI_FLAGS.iter().find(|(flag, _)| flag == arg);
+            if flag.is_none() {
+                other_args.push(arg.clone());
+                continue;
+            }
+            let (flag, setting) = flag.unwrap();
+
+            let value = args.next();
+            if value.is_none() {
+                return Err(AppError::new(&format!(
+                    "expected a value after \"{}\"",
+                    flag
+                )));
+            }
+
+            overrides.values.retain(|(name, _, _)| name != setting);
+            overrides.values.push((
+                setting,
+                value.unwrap().clone(),
+                format!("command line flag {}", flag),
+            ));
+        }
+
+        Ok((overrides, other_args))
+    }
+
+    /// Returns the value of the setting and where it comes from (if specified).
+    pub fn get(&self, setting: &str) -> Option<(&String, &String)> {
+        self.values
+            .iter()
+            .find(|(name, _, _)| *name == setting)
+            .map(|(_, value, origin)| (value, origin))
+    }
+
+    /// Replaces profile values with overridden values.
+    pub fn apply(&self, profile: &mut GfxExportProfile) {
+        for (key, value) in profile.get_values_mut() {
+            if let Some((overridden_value, _)) = self.get(key) {
+                *value = overridden_value.clone();
+            }
+        }
+    }
+
+    /// Returns flags that can be used to override settings.
+    pub fn get_cli_flags() -> Vec<&'static str> {
+        CLI_FLAGS.iter().map(|(flag, _)| *flag).collect()
+    }
+
+    /// Returns pairs of "environment variable" - "setting name".
+    pub fn get_env_variables() -> &'static [(&'static str, &'static str)] {
+        &ENV_VARIABLES
+    }
+}
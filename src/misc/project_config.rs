@@ -0,0 +1,271 @@
+// Std.
+use std::path::{Path, PathBuf};
+
+// External.
+use configparser::ini::Ini;
+
+// Custom.
+use super::config::*;
+use super::error::*;
+
+/// Name of the project config file, usually stored in the root of the game repository.
+const PROJECT_CONFIG_FILE_NAME: &str = ".cryengine-ui-helper.ini";
+const PROJECT_CONFIG_DEFAULT_PROFILE: &str = "default_profile";
+
+/// Profile values specified in the project config, `None` values are taken from the user config.
+#[derive(Debug, Default)]
+pub struct ProfileOverrides {
+    pub name: String,
+    pub path_to_gfxexport_bin: Option<String>,
+    pub additional_gfxexport_args: Option<String>,
+    pub runner: Option<String>,
+    pub gfx_dir: Option<String>,
+    pub xml_dir: Option<String>,
+}
+
+impl ProfileOverrides {
+    /// Replaces profile values with values specified in the project config.
+    pub fn apply(&self, profile: &mut GfxExportProfile) {
+        if let Some(value) = &self.path_to_gfxexport_bin {
+            profile.path_to_gfxexport_bin = value.clone();
+        }
+        if let Some(value) = &self.additional_gfxexport_args {
+            profile.additional_gfxexport_args = value.clone();
+        }
+        if let Some(value) = &self.runner {
+            profile.runner = value.clone();
+        }
+        if let Some(value) = &self.gfx_dir {
+            profile.gfx_dir = value.clone();
+        }
+        if let Some(value) = &self.xml_dir {
+            profile.xml_dir = value.clone();
+        }
+    }
+    /// Returns the value of the setting (named as the config key) if it's specified.
+    pub fn get(&self, key: &str) -> Option<&String> {
+        match key {
+            CONFIG_PATH_TO_GFXEXPORT_BIN => self.path_to_gfxexport_bin.as_ref(),
+            CONFIG_ADDITIONAL_GFXEXPORT_ARGS => self.additional_gfxexport_args.as_ref(),
+            CONFIG_RUNNER => self.runner.as_ref(),
+            CONFIG_GFX_DIR => self.gfx_dir.as_ref(),
+            CONFIG_XML_DIR => self.xml_dir.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Settings shared through the game repository, values specified here
+/// take precedence over values from the user config.
+#[derive(Debug, Default)]
+pub struct ProjectConfig {
+    pub path_to_file: PathBuf,
+    /// Profile to use when no profile was used for the directory of the `.swf` file before.
+    pub default_profile: Option<String>,
+    pub project_fonts: Option<String>,
+    pub profiles: Vec<ProfileOverrides>,
+}
+
+impl ProjectConfig {
+    /// Looks for the project config in the directory of the `.swf` file and its parent directories.
+    ///
+    /// ## Return
+    /// `Ok(None)` if no project config was found, `Err(AppError)` if the found file
+    /// can't be read, otherwise the nearest project config.
+    pub fn find(path_to_swf: &Path) -> Result<Option<ProjectConfig>, AppError> {
+        for dir in path_to_swf.ancestors().skip(1) {
+            let path_to_file = dir.join(PROJECT_CONFIG_FILE_NAME);
+            if path_to_file.is_file() {
+                return Ok(Some(Self::read(&path_to_file)?));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn get_profile_overrides(&self, name: &str) -> Option<&ProfileOverrides> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    fn read(path_to_file: &Path) -> Result<ProjectConfig, AppError> {
+        let mut config = Ini::new_cs();
+        if let Err(e) = config.load(path_to_file) {
+            return Err(AppError::new(&format!(
+                "failed to read the project config \"{}\", error: {}",
+                path_to_file.to_string_lossy(),
+                e
+            )));
+        }
+
+        // Relative paths are relative to the directory of the project config.
+        let config_dir = path_to_file.parent().unwrap_or_else(|| Path::new(""));
+
+        let mut project_config = ProjectConfig {
+            path_to_file: path_to_file.to_path_buf(),
+            default_profile: config
+                .get(CONFIG_GENERAL_SECTION_NAME, PROJECT_CONFIG_DEFAULT_PROFILE),
+            project_fonts: config.get(CONFIG_GENERAL_SECTION_NAME, CONFIG_PROJECT_FONTS),
+            profiles: Vec::new(),
+        };
+
+        for section in config.sections() {
+            let name = section.strip_prefix(CONFIG_PROFILE_SECTION_PREFIX);
+            if name.is_none() {
+                continue;
+            }
+
+            project_config.profiles.push(ProfileOverrides {
+                name: name.unwrap().to_string(),
+                path_to_gfxexport_bin: config
+                    .get(&section, CONFIG_PATH_TO_GFXEXPORT_BIN)
+                    .and_then(|path| Self::resolve_binary_path(config_dir, &path)),
+                additional_gfxexport_args: config.get(&section, CONFIG_ADDITIONAL_GFXEXPORT_ARGS),
+                runner: config.get(&section, CONFIG_RUNNER),
+                gfx_dir: config.get(&section, CONFIG_GFX_DIR),
+                xml_dir: config.get(&section, CONFIG_XML_DIR),
+            });
+        }
+        project_config.profiles.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(project_config)
+    }
+
+    /// Makes a relative path to a binary relative to the directory of the project config,
+    /// names without directories (like `GFxExport`) are kept to be found in `PATH`.
+    ///
+    /// ## Return
+    /// `None` if the path is empty.
+    fn resolve_binary_path(config_dir: &Path, path: &str) -> Option<String> {
+        let path = path.trim();
+        if path.is_empty() {
+            return None;
+        }
+
+        let relative_path = Path::new(path);
+        if relative_path.is_absolute() || relative_path.components().count() == 1 {
+            return Some(path.to_string());
+        }
+
+        Some(config_dir.join(relative_path).to_string_lossy().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use crate::misc::test_utils::TempDir;
+
+    const PROJECT_CONFIG: &[u8] = b"[general]
+default_profile = dds
+project_fonts = Arial, Verdana
+
+[profile.tga]
+path_to_gfxexport_bin = GFxExport
+additional_gfxexport_args = -i TGA
+
+[profile.dds]
+path_to_gfxexport_bin = Tools/GFxExport.exe
+additional_gfxexport_args =
+runner = wine
+
+[profile.empty]
+path_to_gfxexport_bin =
+";
+
+    #[test]
+    fn find_walks_up_from_swf_file() {
+        let dir = TempDir::new("project-config-find");
+        let path_to_swf_dir = dir.get_path().join("Assets").join("UI");
+        fs::create_dir_all(&path_to_swf_dir).unwrap();
+        let path_to_swf = path_to_swf_dir.join("menu.swf");
+
+        assert!(ProjectConfig::find(&path_to_swf).unwrap().is_none());
+
+        let path_to_root_config = dir.write_file(PROJECT_CONFIG_FILE_NAME, PROJECT_CONFIG);
+        let project_config = ProjectConfig::find(&path_to_swf).unwrap().unwrap();
+        assert_eq!(project_config.path_to_file, path_to_root_config);
+
+        // The nearest config is used.
+        let path_to_nearest_config = path_to_swf_dir.join(PROJECT_CONFIG_FILE_NAME);
+        fs::write(&path_to_nearest_config, "[general]\n").unwrap();
+        let project_config = ProjectConfig::find(&path_to_swf).unwrap().unwrap();
+        assert_eq!(project_config.path_to_file, path_to_nearest_config);
+    }
+
+    #[test]
+    fn read_reads_general_values_and_profiles() {
+        let dir = TempDir::new("project-config-read");
+        let path = dir.write_file(PROJECT_CONFIG_FILE_NAME, PROJECT_CONFIG);
+
+        let project_config = ProjectConfig::read(&path).unwrap();
+
+        assert_eq!(project_config.default_profile, Some(String::from("dds")));
+        assert_eq!(
+            project_config.project_fonts,
+            Some(String::from("Arial, Verdana"))
+        );
+        let names = project_config
+            .profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["dds", "empty", "tga"]);
+
+        let dds = project_config.get_profile_overrides("dds").unwrap();
+        assert_eq!(dds.runner, Some(String::from("wine")));
+        // Empty arguments override user arguments.
+        assert_eq!(dds.additional_gfxexport_args, Some(String::new()));
+        assert_eq!(dds.gfx_dir, None);
+    }
+
+    #[test]
+    fn read_resolves_gfxexport_paths() {
+        let dir = TempDir::new("project-config-paths");
+        let path = dir.write_file(PROJECT_CONFIG_FILE_NAME, PROJECT_CONFIG);
+
+        let project_config = ProjectConfig::read(&path).unwrap();
+        let get_path = |name: &str| {
+            project_config
+                .get_profile_overrides(name)
+                .unwrap()
+                .path_to_gfxexport_bin
+                .clone()
+        };
+
+        // Relative to the config directory.
+        assert_eq!(
+            get_path("dds"),
+            Some(
+                dir.get_path()
+                    .join("Tools/GFxExport.exe")
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+        // Found in PATH.
+        assert_eq!(get_path("tga"), Some(String::from("GFxExport")));
+        // Not specified.
+        assert_eq!(get_path("empty"), None);
+    }
+
+    #[test]
+    fn resolve_binary_path_keeps_absolute_paths() {
+        let absolute_path = if cfg!(windows) {
+            "C:\\Tools\\GFxExport.exe"
+        } else {
+            "/opt/tools/gfxexport"
+        };
+
+        assert_eq!(
+            ProjectConfig::resolve_binary_path(Path::new("project"), absolute_path),
+            Some(absolute_path.to_string())
+        );
+        assert_eq!(
+            ProjectConfig::resolve_binary_path(Path::new("project"), "  "),
+            None
+        );
+    }
+}
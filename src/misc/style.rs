@@ -29,6 +29,7 @@ impl application::StyleSheet for Theme {
 pub enum Container {
     #[default]
     Invisible,
+    Panel,
 }
 
 impl container::StyleSheet for Theme {
@@ -37,6 +38,13 @@ impl container::StyleSheet for Theme {
     fn appearance(&self, style: Self::Style) -> container::Appearance {
         match style {
             Container::Invisible => container::Appearance::default(),
+            Container::Panel => container::Appearance {
+                background: Some(Background::Color(self.palette().base.foreground)),
+                border_radius: 5.0,
+                border_width: 1.0,
+                border_color: self.palette().normal.primary,
+                ..container::Appearance::default()
+            },
         }
     }
 }
@@ -198,6 +206,9 @@ pub enum TextInput {
     #[default]
     Default,
     Special,
+    Error,
+    /// Matches the search filter.
+    Highlighted,
 }
 
 impl text_input::StyleSheet for Theme {
@@ -217,6 +228,18 @@ impl text_input::StyleSheet for Theme {
                 border_width: 1.0,
                 border_color: self.palette().normal.secondary,
             },
+            TextInput::Error => text_input::Appearance {
+                background: Background::Color(self.palette().base.foreground),
+                border_radius: 5.0,
+                border_width: 1.0,
+                border_color: self.palette().normal.dangerous,
+            },
+            TextInput::Highlighted => text_input::Appearance {
+                background: Background::Color(self.palette().base.foreground),
+                border_radius: 5.0,
+                border_width: 2.0,
+                border_color: self.palette().normal.alt,
+            },
         }
     }
 
@@ -240,6 +263,18 @@ impl text_input::StyleSheet for Theme {
                     ..self.palette().bright.secondary
                 },
             },
+            TextInput::Error => text_input::Appearance {
+                background: Background::Color(self.palette().base.foreground),
+                border_radius: 2.0,
+                border_width: 1.0,
+                border_color: self.palette().bright.dangerous,
+            },
+            TextInput::Highlighted => text_input::Appearance {
+                background: Background::Color(self.palette().base.foreground),
+                border_radius: 2.0,
+                border_width: 2.0,
+                border_color: self.palette().bright.alt,
+            },
         }
     }
 
@@ -316,6 +351,11 @@ impl pick_list::StyleSheet for Theme {
 pub enum Text {
     #[default]
     Default,
+    Warning,
+    Added,
+    Removed,
+    /// Matches the search filter.
+    Highlighted,
     Color(Color),
 }
 
@@ -331,6 +371,18 @@ impl text::StyleSheet for Theme {
     fn appearance(&self, style: Self::Style) -> text::Appearance {
         match style {
             Text::Default => Default::default(),
+            Text::Warning => text::Appearance {
+                color: Some(self.palette().bright.dangerous),
+            },
+            Text::Added => text::Appearance {
+                color: Some(self.palette().bright.alt),
+            },
+            Text::Removed => text::Appearance {
+                color: Some(self.palette().bright.dangerous),
+            },
+            Text::Highlighted => text::Appearance {
+                color: Some(self.palette().bright.alt),
+            },
             Text::Color(c) => text::Appearance { color: Some(c) },
         }
     }
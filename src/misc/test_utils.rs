@@ -0,0 +1,49 @@
+// Std.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Empty directory in the system temp directory that is removed when dropped.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates an empty directory.
+    ///
+    /// ## Arguments
+    /// * `name`: name of the directory, should be unique among tests
+    ///   because tests run in parallel.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!(
+            "cryengine-ui-helper-test-{}-{}",
+            process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        Self { path }
+    }
+
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes the file to the directory.
+    ///
+    /// ## Return
+    /// Path to the written file.
+    pub fn write_file(&self, name: &str, data: &[u8]) -> PathBuf {
+        let path = self.path.join(name);
+        fs::write(&path, data).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}